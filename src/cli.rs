@@ -12,6 +12,14 @@ pub struct Cli {
     #[arg(short = 't', long = "theme")]
     pub theme: Option<String>,
 
+    /// Color depth to render with, overriding config and detection
+    #[arg(long = "color", value_name = "DEPTH", value_parser = ["auto", "truecolor", "256", "16", "none"])]
+    pub color: Option<String>,
+
+    /// Output format of the statusline
+    #[arg(long = "format", value_name = "FORMAT", value_parser = ["ansi", "plain", "json", "tmux", "zsh", "bash", "html"])]
+    pub format: Option<String>,
+
     /// Print current configuration
     #[arg(long = "print")]
     pub print: bool,
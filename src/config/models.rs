@@ -1,6 +1,9 @@
+use super::AnsiColor;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelConfig {
@@ -8,11 +11,177 @@ pub struct ModelConfig {
     pub model_entries: Vec<ModelEntry>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ModelEntry {
     pub pattern: String,
+    /// How `pattern` is compared against the model id, always case-insensitive
+    #[serde(
+        rename = "match",
+        default,
+        skip_serializing_if = "MatchKind::is_default"
+    )]
+    pub match_kind: MatchKind,
+    /// May reference capture groups of regex and glob patterns as `{1}`, `{2}`, ...
     pub display_name: String,
     pub context_limit: u32,
+    /// Backend serving the model (e.g. a proxy name), shown as a badge by the Model and Usage segments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Tokens the backend reserves for a response; the Usage segment measures
+    /// the prompt against the context left after this reservation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<ModelPricing>,
+    /// Whether usage reports cache tokens separately from input tokens (Anthropic style).
+    /// OpenAI-compatible backends count cached tokens inside the input, so set this to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reports_cache: Option<bool>,
+    /// Overrides the Model segment icon and text color for this entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<AnsiColor>,
+    /// Compiled glob or regex pattern, filled in when the config loads
+    #[serde(skip)]
+    matcher: OnceLock<Option<Regex>>,
+}
+
+/// Prices in USD per million tokens
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    pub input: f64,
+    pub output: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchKind {
+    /// Pattern appears anywhere in the model id
+    #[default]
+    Substring,
+    /// Model id starts with the pattern
+    Prefix,
+    /// Whole model id matches a `*`/`?` glob; each wildcard is a capture group
+    Glob,
+    /// Model id matches a regular expression (unanchored)
+    Regex,
+}
+
+impl MatchKind {
+    fn is_default(&self) -> bool {
+        *self == MatchKind::Substring
+    }
+}
+
+/// Matches `{n}` placeholders in display names
+fn placeholder_regex() -> &'static Regex {
+    static PLACEHOLDER: OnceLock<Regex> = OnceLock::new();
+    PLACEHOLDER.get_or_init(|| Regex::new(r"\{(\d+)\}").expect("valid placeholder regex"))
+}
+
+impl ModelEntry {
+    /// Compile glob and regex patterns; substring and prefix patterns need no regex
+    fn compile(&self) -> Result<Option<Regex>, regex::Error> {
+        let source = match self.match_kind {
+            MatchKind::Substring | MatchKind::Prefix => return Ok(None),
+            MatchKind::Regex => self.pattern.clone(),
+            MatchKind::Glob => {
+                let mut source = String::from("^");
+                for c in self.pattern.chars() {
+                    match c {
+                        '*' => source.push_str("(.*)"),
+                        '?' => source.push_str("(.)"),
+                        c => source.push_str(&regex::escape(&c.to_string())),
+                    }
+                }
+                source.push('$');
+                source
+            }
+        };
+
+        RegexBuilder::new(&source)
+            .case_insensitive(true)
+            .build()
+            .map(Some)
+    }
+
+    /// Compiled pattern, built once per entry; invalid patterns yield `None`
+    fn matcher(&self) -> Option<&Regex> {
+        self.matcher
+            .get_or_init(|| self.compile().ok().flatten())
+            .as_ref()
+    }
+
+    /// Tokens available to the prompt: the context limit minus the output reservation
+    pub fn input_limit(&self) -> u32 {
+        self.context_limit
+            .saturating_sub(self.max_output_tokens.unwrap_or(0))
+            .max(1)
+    }
+
+    /// Capture groups of a successful match, index 0 being the whole match.
+    /// Invalid patterns never match; `ModelConfig::validate` reports them.
+    fn match_id(&self, model_id: &str) -> Option<Vec<String>> {
+        let model_lower = model_id.to_lowercase();
+        let pattern_lower = self.pattern.to_lowercase();
+
+        match self.match_kind {
+            MatchKind::Substring => model_lower
+                .contains(&pattern_lower)
+                .then(|| vec![self.pattern.clone()]),
+            MatchKind::Prefix => model_lower
+                .starts_with(&pattern_lower)
+                .then(|| vec![self.pattern.clone()]),
+            MatchKind::Glob | MatchKind::Regex => {
+                let captures = self.matcher()?.captures(model_id)?;
+                Some(
+                    captures
+                        .iter()
+                        .map(|group| group.map(|m| m.as_str().to_string()).unwrap_or_default())
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    /// Replace `{n}` placeholders in the display name with capture groups
+    fn render_display_name(&self, captures: &[String]) -> String {
+        placeholder_regex()
+            .replace_all(&self.display_name, |caps: &regex::Captures| {
+                caps[1]
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| captures.get(index))
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .to_string()
+    }
+
+    /// Check that the pattern compiles and every placeholder refers to an existing group
+    fn validate(&self) -> Result<(), String> {
+        let regex = self
+            .compile()
+            .map_err(|e| format!("invalid pattern \"{}\": {}", self.pattern, e))?;
+        let groups = regex.map(|r| r.captures_len() - 1).unwrap_or(0);
+
+        for caps in placeholder_regex().captures_iter(&self.display_name) {
+            let index: usize = caps[1].parse().unwrap_or(usize::MAX);
+            if index > groups {
+                return Err(format!(
+                    "display_name \"{}\" references {{{}}} but pattern \"{}\" has {} capture group(s)",
+                    self.display_name, index, self.pattern, groups
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl ModelConfig {
@@ -20,10 +189,64 @@ impl ModelConfig {
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
         let content = fs::read_to_string(path)?;
         let config: ModelConfig = toml::from_str(&content)?;
+        config.compile_matchers();
         Ok(config)
     }
 
-    /// Load model configuration with fallback locations
+    /// Compile every entry's pattern up front so matching never recompiles it
+    fn compile_matchers(&self) {
+        for entry in &self.model_entries {
+            entry.matcher();
+        }
+    }
+
+    /// Candidate models.toml locations: user config directory first, then local
+    fn config_paths() -> Vec<PathBuf> {
+        [
+            dirs::home_dir().map(|d| d.join(".claude").join("ccline").join("models.toml")),
+            Some(Path::new("models.toml").to_path_buf()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Validate every entry, reporting all problems at once
+    pub fn validate(&self) -> Result<(), String> {
+        let errors: Vec<String> = self
+            .model_entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                entry
+                    .validate()
+                    .err()
+                    .map(|e| format!("models[{}]: {}", index, e))
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("\n"))
+        }
+    }
+
+    /// Parse and validate the models.toml that `load` would use, for `--check`
+    pub fn check() -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(path) = Self::config_paths().into_iter().find(|p| p.exists()) {
+            let config =
+                Self::load_from_file(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            config
+                .validate()
+                .map_err(|e| format!("{}:\n{}", path.display(), e))?;
+        }
+        Ok(())
+    }
+
+    /// Load model configuration with fallback locations.
+    /// Problems are reported on stderr: a file that fails to parse is skipped,
+    /// invalid entries are kept but never match.
     pub fn load() -> Self {
         let mut model_config = Self::default();
 
@@ -35,20 +258,21 @@ impl ModelConfig {
             }
         }
 
-        // Try loading from user config directory first, then local
-        let config_paths = [
-            dirs::home_dir().map(|d| d.join(".claude").join("ccline").join("models.toml")),
-            Some(Path::new("models.toml").to_path_buf()),
-        ];
-
-        for path in config_paths.iter().flatten() {
+        for path in Self::config_paths() {
             if path.exists() {
-                if let Ok(config) = Self::load_from_file(path) {
-                    // Prepend external models to built-in ones for priority
-                    let mut merged_entries = config.model_entries;
-                    merged_entries.extend(model_config.model_entries);
-                    model_config.model_entries = merged_entries;
-                    return model_config;
+                match Self::load_from_file(&path) {
+                    Ok(config) => {
+                        if let Err(e) = config.validate() {
+                            eprintln!("ccline: {}:\n{}", path.display(), e);
+                        }
+
+                        // Prepend external models to built-in ones for priority
+                        let mut merged_entries = config.model_entries;
+                        merged_entries.extend(model_config.model_entries);
+                        model_config.model_entries = merged_entries;
+                        return model_config;
+                    }
+                    Err(e) => eprintln!("ccline: ignoring {}: {}", path.display(), e),
                 }
             }
         }
@@ -57,35 +281,30 @@ impl ModelConfig {
         model_config
     }
 
+    /// First entry matching the model id, giving access to its provider profile
+    pub fn get_entry(&self, model_id: &str) -> Option<&ModelEntry> {
+        self.model_entries
+            .iter()
+            .find(|entry| entry.match_id(model_id).is_some())
+    }
+
     /// Get context limit for a model based on ID pattern matching
     /// Checks external config first, then falls back to built-in config
     pub fn get_context_limit(&self, model_id: &str) -> u32 {
-        let model_lower = model_id.to_lowercase();
-
-        // Check model entries
-        for entry in &self.model_entries {
-            if model_lower.contains(&entry.pattern.to_lowercase()) {
-                return entry.context_limit;
-            }
-        }
-
-        200_000
+        self.get_entry(model_id)
+            .map(|entry| entry.context_limit)
+            .unwrap_or(200_000)
     }
 
     /// Get display name for a model based on ID pattern matching
     /// Checks external config first, then falls back to built-in config
     /// Returns None if no match found (should use fallback display_name)
     pub fn get_display_name(&self, model_id: &str) -> Option<String> {
-        let model_lower = model_id.to_lowercase();
-
-        // Check model entries
-        for entry in &self.model_entries {
-            if model_lower.contains(&entry.pattern.to_lowercase()) {
-                return Some(entry.display_name.clone());
-            }
-        }
-
-        None
+        self.model_entries.iter().find_map(|entry| {
+            entry
+                .match_id(model_id)
+                .map(|captures| entry.render_display_name(&captures))
+        })
     }
 
     /// Create default model configuration file with minimal template
@@ -113,7 +332,16 @@ impl ModelConfig {
              # [[models]]\n\
              # pattern = \"glm-4.5\"\n\
              # display_name = \"GLM-4.5\"\n\
-             # context_limit = 128000\n",
+             # context_limit = 128000\n\
+             \n\
+             # Patterns match as case-insensitive substrings by default. Set `match` to\n\
+             # \"prefix\", \"glob\" or \"regex\" for stricter matching; regex groups and glob\n\
+             # wildcards can be used in display_name as {{1}}, {{2}}, ...\n\
+             # [[models]]\n\
+             # pattern = \"claude-opus-(\\\\d+)-(\\\\d+)\"\n\
+             # match = \"regex\"\n\
+             # display_name = \"Opus {{1}}.{{2}}\"\n\
+             # context_limit = 200000\n",
             toml_content.trim()
         );
 
@@ -129,61 +357,218 @@ impl ModelConfig {
 
 impl Default for ModelConfig {
     fn default() -> Self {
-        Self {
+        let config = Self {
             model_entries: vec![
                 // 1M context models (put first for priority matching)
                 ModelEntry {
                     pattern: "[1m]".to_string(),
+                    match_kind: MatchKind::Substring,
                     display_name: "Sonnet 4 1M".to_string(),
                     context_limit: 1_000_000,
+                    ..Default::default()
                 },
+                // Covers claude-sonnet-4, claude-4-sonnet and bare sonnet-4 ids
                 ModelEntry {
-                    pattern: "claude-sonnet-4".to_string(),
-                    display_name: "Sonnet 4".to_string(),
-                    context_limit: 200_000,
-                },
-                ModelEntry {
-                    pattern: "claude-4-sonnet".to_string(),
+                    pattern: "sonnet-4|4-sonnet".to_string(),
+                    match_kind: MatchKind::Regex,
                     display_name: "Sonnet 4".to_string(),
                     context_limit: 200_000,
+                    ..Default::default()
                 },
                 ModelEntry {
                     pattern: "claude-4-opus".to_string(),
+                    match_kind: MatchKind::Substring,
                     display_name: "Opus 4".to_string(),
                     context_limit: 200_000,
-                },
-                ModelEntry {
-                    pattern: "sonnet-4".to_string(),
-                    display_name: "Sonnet 4".to_string(),
-                    context_limit: 200_000,
+                    ..Default::default()
                 },
                 ModelEntry {
                     pattern: "claude-3-7-sonnet".to_string(),
+                    match_kind: MatchKind::Substring,
                     display_name: "Sonnet 3.7".to_string(),
                     context_limit: 200_000,
+                    ..Default::default()
                 },
                 // Third-party models
                 ModelEntry {
                     pattern: "glm-4.5".to_string(),
+                    match_kind: MatchKind::Substring,
                     display_name: "GLM-4.5".to_string(),
                     context_limit: 128_000,
+                    ..Default::default()
                 },
                 ModelEntry {
                     pattern: "kimi-k2-turbo".to_string(),
+                    match_kind: MatchKind::Substring,
                     display_name: "Kimi K2 Turbo".to_string(),
                     context_limit: 128_000,
+                    ..Default::default()
                 },
                 ModelEntry {
                     pattern: "kimi-k2".to_string(),
+                    match_kind: MatchKind::Substring,
                     display_name: "Kimi K2".to_string(),
                     context_limit: 128_000,
+                    ..Default::default()
                 },
                 ModelEntry {
                     pattern: "qwen3-coder".to_string(),
+                    match_kind: MatchKind::Substring,
                     display_name: "Qwen Coder".to_string(),
                     context_limit: 256_000,
+                    ..Default::default()
                 },
             ],
+        };
+        config.compile_matchers();
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pattern: &str, match_kind: MatchKind, display_name: &str) -> ModelEntry {
+        ModelEntry {
+            pattern: pattern.to_string(),
+            match_kind,
+            display_name: display_name.to_string(),
+            context_limit: 200_000,
+            ..Default::default()
         }
     }
+
+    #[test]
+    fn substring_and_prefix_ignore_case() {
+        let substring = entry("Sonnet", MatchKind::Substring, "Sonnet");
+        assert_eq!(
+            substring.match_id("claude-sonnet-4"),
+            Some(vec!["Sonnet".to_string()])
+        );
+        assert_eq!(substring.match_id("claude-opus-4"), None);
+
+        let prefix = entry("claude-", MatchKind::Prefix, "Claude");
+        assert!(prefix.match_id("Claude-Opus-4").is_some());
+        assert!(prefix.match_id("my-claude-opus").is_none());
+    }
+
+    #[test]
+    fn glob_captures_each_wildcard() {
+        let glob = entry("claude-*-4-?", MatchKind::Glob, "{1} 4.{2}");
+        let captures = glob.match_id("claude-opus-4-1").unwrap();
+        assert_eq!(captures, vec!["claude-opus-4-1", "opus", "1"]);
+        assert_eq!(glob.render_display_name(&captures), "opus 4.1");
+        // Globs must match the whole id
+        assert!(glob.match_id("x-claude-opus-4-1").is_none());
+        assert!(glob.match_id("claude-opus-4-12").is_none());
+    }
+
+    #[test]
+    fn glob_escapes_regex_characters() {
+        let glob = entry("gpt-4.1*", MatchKind::Glob, "GPT");
+        assert!(glob.match_id("gpt-4.1-mini").is_some());
+        assert!(glob.match_id("gpt-401").is_none());
+    }
+
+    #[test]
+    fn regex_captures_groups_case_insensitively() {
+        let regex = entry(r"(sonnet|opus)-(\d+)", MatchKind::Regex, "{1} v{2}");
+        let captures = regex.match_id("Claude-Opus-4-20250514").unwrap();
+        assert_eq!(captures, vec!["Opus-4", "Opus", "4"]);
+        assert_eq!(regex.render_display_name(&captures), "Opus v4");
+    }
+
+    #[test]
+    fn unmatched_optional_group_renders_empty() {
+        let regex = entry(r"kimi-k2(-turbo)?", MatchKind::Regex, "Kimi K2{1}");
+        let captures = regex.match_id("kimi-k2").unwrap();
+        assert_eq!(captures, vec!["kimi-k2", ""]);
+        assert_eq!(regex.render_display_name(&captures), "Kimi K2");
+    }
+
+    #[test]
+    fn render_display_name_leaves_plain_names_alone() {
+        let plain = entry("glm", MatchKind::Substring, "GLM {x} {}");
+        assert_eq!(
+            plain.render_display_name(&["glm".to_string()]),
+            "GLM {x} {}"
+        );
+        // Placeholders past the last group render empty
+        let missing = entry("glm", MatchKind::Substring, "GLM{3}");
+        assert_eq!(missing.render_display_name(&["glm".to_string()]), "GLM");
+    }
+
+    #[test]
+    fn invalid_pattern_never_matches_and_fails_validation() {
+        let invalid = entry("(sonnet", MatchKind::Regex, "Sonnet");
+        assert!(invalid.match_id("sonnet").is_none());
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_missing_capture_groups() {
+        assert!(entry("claude-*", MatchKind::Glob, "{1}").validate().is_ok());
+        assert!(entry("claude-*", MatchKind::Glob, "{2}")
+            .validate()
+            .is_err());
+        assert!(entry("sonnet", MatchKind::Substring, "{1}")
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn input_limit_leaves_room_for_output() {
+        let mut profile = entry("glm", MatchKind::Substring, "GLM");
+        assert_eq!(profile.input_limit(), 200_000);
+        profile.max_output_tokens = Some(32_000);
+        assert_eq!(profile.input_limit(), 168_000);
+        profile.max_output_tokens = Some(300_000);
+        assert_eq!(profile.input_limit(), 1);
+    }
+
+    #[test]
+    fn profile_fields_parse_from_toml() {
+        let config: ModelConfig = toml::from_str(
+            r#"
+            [[models]]
+            pattern = "glm"
+            display_name = "GLM"
+            context_limit = 128000
+            max_output_tokens = 8192
+            pricing = { input = 0.6, output = 2.2, cache_read = 0.11 }
+            "#,
+        )
+        .unwrap();
+        let profile = &config.model_entries[0];
+        assert_eq!(profile.max_output_tokens, Some(8192));
+        assert_eq!(
+            profile.pricing,
+            Some(ModelPricing {
+                input: 0.6,
+                output: 2.2,
+                cache_read: Some(0.11),
+                cache_write: None,
+            })
+        );
+    }
+
+    #[test]
+    fn first_matching_entry_wins() {
+        let config = ModelConfig {
+            model_entries: vec![
+                entry("kimi-k2-turbo", MatchKind::Substring, "Kimi K2 Turbo"),
+                entry("kimi-(k\\d)", MatchKind::Regex, "Kimi {1}"),
+            ],
+        };
+        assert_eq!(
+            config.get_display_name("kimi-k2-turbo-preview").as_deref(),
+            Some("Kimi K2 Turbo")
+        );
+        assert_eq!(
+            config.get_display_name("kimi-k2").as_deref(),
+            Some("Kimi k2")
+        );
+        assert_eq!(config.get_display_name("gpt-4"), None);
+    }
 }
@@ -7,14 +7,149 @@ pub struct Config {
     pub style: StyleConfig,
     pub segments: Vec<SegmentConfig>,
     pub theme: String,
+    /// Optional multi-line layout; without rows every segment goes on one line
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rows: Vec<RowConfig>,
+    #[serde(default)]
+    pub terminal: TerminalConfig,
 }
 
 // Default implementation moved to ui/themes/presets.rs
 
+/// One line of the statusline, drawn in the order its segments are listed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RowConfig {
+    pub segments: Vec<SegmentId>,
+    /// Overrides `style.separator` for this line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separator: Option<String>,
+}
+
+/// How lines are fitted to the terminal
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TerminalConfig {
+    /// Width in columns; falls back to `COLUMNS`. Without a width lines are
+    /// neither padded nor truncated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<usize>,
+    /// Count East Asian ambiguous-width characters (`·`, `…`, box drawing) as
+    /// two columns, as CJK locales usually render them
+    #[serde(default)]
+    pub ambiguous_wide: bool,
+    /// Count private-use glyphs such as Nerd Font icons as two columns, for
+    /// non-Mono Nerd Fonts
+    #[serde(default)]
+    pub private_use_wide: bool,
+    /// Colors the terminal can display; detected from the environment when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_depth: Option<ColorDepth>,
+}
+
+/// Color capability of the terminal, from least to most capable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ColorDepth {
+    /// No escape sequences at all, as requested by `NO_COLOR`
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "16")]
+    Ansi16,
+    #[serde(rename = "256")]
+    Ansi256,
+    #[serde(rename = "truecolor")]
+    TrueColor,
+}
+
+impl ColorDepth {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "none" => Some(ColorDepth::None),
+            "16" => Some(ColorDepth::Ansi16),
+            "256" => Some(ColorDepth::Ansi256),
+            "truecolor" | "24bit" => Some(ColorDepth::TrueColor),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StyleConfig {
     pub mode: StyleMode,
+    /// Drawn between segments; Powerline glyphs blend the neighbouring backgrounds
     pub separator: String,
+    /// Drawn before the first segment of each block, e.g. `\u{e0b6}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_cap: Option<String>,
+    /// Drawn after the last segment of each block, e.g. `\u{e0b4}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_cap: Option<String>,
+    /// Color of text and thin separators; text separators default to white
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separator_color: Option<SeparatorColor>,
+    /// Colors for the semantic roles segments give parts of their text
+    #[serde(default, skip_serializing_if = "RoleColors::is_empty")]
+    pub roles: RoleColors,
+}
+
+/// What a part of a segment's text means, so themes can color it consistently
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpanRole {
+    /// The number or name the segment is about, e.g. `42%`
+    Value,
+    /// The unit after a value, e.g. `tokens`
+    Unit,
+    /// Something that needs attention, e.g. uncommitted changes
+    Warning,
+    /// Supporting detail, e.g. a commit hash
+    Muted,
+    /// A gain, e.g. added lines
+    Positive,
+    /// A loss, e.g. removed lines
+    Negative,
+}
+
+/// Theme colors for span roles. Unset roles use the segment's color, except
+/// muted text, which is dimmed instead.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoleColors {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<AnsiColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<AnsiColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<AnsiColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub muted: Option<AnsiColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub positive: Option<AnsiColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub negative: Option<AnsiColor>,
+}
+
+impl RoleColors {
+    pub fn color(&self, role: SpanRole) -> Option<&AnsiColor> {
+        match role {
+            SpanRole::Value => self.value.as_ref(),
+            SpanRole::Unit => self.unit.as_ref(),
+            SpanRole::Warning => self.warning.as_ref(),
+            SpanRole::Muted => self.muted.as_ref(),
+            SpanRole::Positive => self.positive.as_ref(),
+            SpanRole::Negative => self.negative.as_ref(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeparatorColor {
+    /// `"inherit"`: the text color of the segment before the separator
+    Inherit,
+    #[serde(untagged)]
+    Color(AnsiColor),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -32,9 +167,36 @@ pub struct SegmentConfig {
     pub icon: IconConfig,
     pub colors: ColorConfig,
     pub styles: TextStyleConfig,
+    #[serde(default, skip_serializing_if = "SegmentLayout::is_default")]
+    pub layout: SegmentLayout,
     pub options: HashMap<String, serde_json::Value>,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Alignment {
+    #[default]
+    Left,
+    Right,
+}
+
+/// Where a segment sits in its row and how readily it gives way on overflow
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SegmentLayout {
+    #[serde(default)]
+    pub align: Alignment,
+    /// Lower priorities are shortened and then dropped first; ties give way
+    /// from the end of the line
+    #[serde(default)]
+    pub priority: u8,
+}
+
+impl SegmentLayout {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IconConfig {
     pub plain: String,
@@ -46,11 +208,186 @@ pub struct ColorConfig {
     pub icon: Option<AnsiColor>,
     pub text: Option<AnsiColor>,
     pub background: Option<AnsiColor>,
+    /// Color of the secondary text; falls back to the text color
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<AnsiColor>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct TextStyleConfig {
     pub text_bold: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub text_italic: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub text_underline: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub text_dim: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub text_strikethrough: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub text_reverse: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub icon_bold: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub icon_italic: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub icon_underline: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub icon_dim: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub icon_strikethrough: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub icon_reverse: bool,
+    /// Secondary text attributes; unset ones follow the matching text attribute
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_bold: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_italic: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_underline: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_dim: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_strikethrough: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_reverse: Option<bool>,
+    /// Shape of underlines, for terminals that support styled underlines
+    #[serde(default, skip_serializing_if = "UnderlineStyle::is_default")]
+    pub underline_style: UnderlineStyle,
+    /// Underline color, for terminals that support colored underlines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub underline_color: Option<AnsiColor>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnderlineStyle {
+    #[default]
+    Straight,
+    Double,
+    Curly,
+    Dotted,
+    Dashed,
+}
+
+impl UnderlineStyle {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Which part of a segment a text attribute applies to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StyleTarget {
+    Icon,
+    Text,
+    Secondary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextAttribute {
+    Bold,
+    Italic,
+    Underline,
+    Dim,
+    Strikethrough,
+    Reverse,
+}
+
+impl TextAttribute {
+    pub const ALL: [TextAttribute; 6] = [
+        TextAttribute::Bold,
+        TextAttribute::Italic,
+        TextAttribute::Underline,
+        TextAttribute::Dim,
+        TextAttribute::Strikethrough,
+        TextAttribute::Reverse,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextAttribute::Bold => "Bold",
+            TextAttribute::Italic => "Italic",
+            TextAttribute::Underline => "Underline",
+            TextAttribute::Dim => "Dim",
+            TextAttribute::Strikethrough => "Strike",
+            TextAttribute::Reverse => "Reverse",
+        }
+    }
+}
+
+impl TextStyleConfig {
+    fn flag_mut(&mut self, target: StyleTarget, attribute: TextAttribute) -> &mut bool {
+        match (target, attribute) {
+            (StyleTarget::Icon, TextAttribute::Bold) => &mut self.icon_bold,
+            (StyleTarget::Icon, TextAttribute::Italic) => &mut self.icon_italic,
+            (StyleTarget::Icon, TextAttribute::Underline) => &mut self.icon_underline,
+            (StyleTarget::Icon, TextAttribute::Dim) => &mut self.icon_dim,
+            (StyleTarget::Icon, TextAttribute::Strikethrough) => &mut self.icon_strikethrough,
+            (StyleTarget::Icon, TextAttribute::Reverse) => &mut self.icon_reverse,
+            // Secondary flags are optional and handled by `secondary_mut`
+            (_, TextAttribute::Bold) => &mut self.text_bold,
+            (_, TextAttribute::Italic) => &mut self.text_italic,
+            (_, TextAttribute::Underline) => &mut self.text_underline,
+            (_, TextAttribute::Dim) => &mut self.text_dim,
+            (_, TextAttribute::Strikethrough) => &mut self.text_strikethrough,
+            (_, TextAttribute::Reverse) => &mut self.text_reverse,
+        }
+    }
+
+    fn secondary_mut(&mut self, attribute: TextAttribute) -> &mut Option<bool> {
+        match attribute {
+            TextAttribute::Bold => &mut self.secondary_bold,
+            TextAttribute::Italic => &mut self.secondary_italic,
+            TextAttribute::Underline => &mut self.secondary_underline,
+            TextAttribute::Dim => &mut self.secondary_dim,
+            TextAttribute::Strikethrough => &mut self.secondary_strikethrough,
+            TextAttribute::Reverse => &mut self.secondary_reverse,
+        }
+    }
+
+    pub fn is_set(&self, target: StyleTarget, attribute: TextAttribute) -> bool {
+        match (target, attribute) {
+            (StyleTarget::Text, TextAttribute::Bold) => self.text_bold,
+            (StyleTarget::Text, TextAttribute::Italic) => self.text_italic,
+            (StyleTarget::Text, TextAttribute::Underline) => self.text_underline,
+            (StyleTarget::Text, TextAttribute::Dim) => self.text_dim,
+            (StyleTarget::Text, TextAttribute::Strikethrough) => self.text_strikethrough,
+            (StyleTarget::Text, TextAttribute::Reverse) => self.text_reverse,
+            (StyleTarget::Icon, TextAttribute::Bold) => self.icon_bold,
+            (StyleTarget::Icon, TextAttribute::Italic) => self.icon_italic,
+            (StyleTarget::Icon, TextAttribute::Underline) => self.icon_underline,
+            (StyleTarget::Icon, TextAttribute::Dim) => self.icon_dim,
+            (StyleTarget::Icon, TextAttribute::Strikethrough) => self.icon_strikethrough,
+            (StyleTarget::Icon, TextAttribute::Reverse) => self.icon_reverse,
+            (StyleTarget::Secondary, attribute) => {
+                let secondary = match attribute {
+                    TextAttribute::Bold => self.secondary_bold,
+                    TextAttribute::Italic => self.secondary_italic,
+                    TextAttribute::Underline => self.secondary_underline,
+                    TextAttribute::Dim => self.secondary_dim,
+                    TextAttribute::Strikethrough => self.secondary_strikethrough,
+                    TextAttribute::Reverse => self.secondary_reverse,
+                };
+                secondary.unwrap_or_else(|| self.is_set(StyleTarget::Text, attribute))
+            }
+        }
+    }
+
+    /// Flip an attribute and return its new state. Flipping a secondary
+    /// attribute pins it, so it no longer follows the text attribute.
+    pub fn toggle(&mut self, target: StyleTarget, attribute: TextAttribute) -> bool {
+        let enabled = !self.is_set(target, attribute);
+        match target {
+            StyleTarget::Secondary => *self.secondary_mut(attribute) = Some(enabled),
+            StyleTarget::Icon | StyleTarget::Text => *self.flag_mut(target, attribute) = enabled,
+        }
+        enabled
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +409,13 @@ pub enum SegmentId {
     Session,
     OutputStyle,
     Update,
+    Commit,
+    PullRequest,
+    Project,
+    Environment,
+    System,
+    Clock,
+    Productivity,
 }
 
 // Legacy compatibility structure
@@ -218,6 +562,17 @@ impl NormalizedUsage {
         }
     }
 
+    /// Context tokens for backends whose input count already includes cached
+    /// tokens, where adding the cache fields would count them twice
+    pub fn context_tokens_excluding_cache(&self) -> u32 {
+        let context = self.input_tokens + self.output_tokens;
+        if context > 0 {
+            context
+        } else {
+            self.total_tokens
+        }
+    }
+
     /// Get the most appropriate token count for general display
     /// For OpenAI format: use total_tokens directly
     /// For Anthropic format: use context_tokens (input + cache)
@@ -246,10 +601,18 @@ impl Config {
         // Compare style config
         if self.style.mode != theme_preset.style.mode
             || self.style.separator != theme_preset.style.separator
+            || self.style.start_cap != theme_preset.style.start_cap
+            || self.style.end_cap != theme_preset.style.end_cap
+            || self.style.separator_color != theme_preset.style.separator_color
+            || self.style.roles != theme_preset.style.roles
         {
             return false;
         }
 
+        if self.rows != theme_preset.rows {
+            return false;
+        }
+
         // Compare segments count and order
         if self.segments.len() != theme_preset.segments.len() {
             return false;
@@ -270,6 +633,77 @@ impl Config {
         !self.matches_theme(&self.theme)
     }
 
+    /// Row a segment is drawn on; segments not assigned to a row go on the first line
+    pub fn row_of(&self, id: SegmentId) -> usize {
+        self.rows
+            .iter()
+            .position(|row| row.segments.contains(&id))
+            .unwrap_or(0)
+    }
+
+    /// Move a segment to another row, creating rows as needed; rows other than
+    /// the first are dropped once they are left empty
+    pub fn set_row(&mut self, id: SegmentId, row: usize) {
+        self.fill_first_row();
+        for existing in self.rows.iter_mut() {
+            existing.segments.retain(|segment| *segment != id);
+        }
+        if self.rows.len() <= row {
+            self.rows.resize_with(row + 1, RowConfig::default);
+        }
+        self.rows[row].segments.push(id);
+        self.place_in_row(id);
+
+        let mut index = 0;
+        self.rows.retain(|row| {
+            index += 1;
+            index == 1 || !row.segments.is_empty()
+        });
+        // A single line with the default separator needs no layout
+        if self.rows.len() == 1 && self.rows[0].separator.is_none() {
+            self.rows.clear();
+        }
+    }
+
+    /// List the segments drawn on the first line without being assigned to a
+    /// row explicitly, after the assigned ones as the renderer draws them, so
+    /// inserting into the first line cannot reorder it
+    fn fill_first_row(&mut self) {
+        if self.rows.is_empty() {
+            self.rows.push(RowConfig::default());
+        }
+        let unassigned: Vec<SegmentId> = self
+            .segments
+            .iter()
+            .map(|segment| segment.id)
+            .filter(|id| !self.rows.iter().any(|row| row.segments.contains(id)))
+            .collect();
+        self.rows[0].segments.extend(unassigned);
+    }
+
+    /// Move a segment within its row so it precedes the first row member that
+    /// comes after it in the segment list; the other segments keep their order
+    pub fn place_in_row(&mut self, id: SegmentId) {
+        let segments = &self.segments;
+        let position = |id: &SegmentId| {
+            segments
+                .iter()
+                .position(|segment| segment.id == *id)
+                .unwrap_or(usize::MAX)
+        };
+        let Some(row) = self.rows.iter_mut().find(|row| row.segments.contains(&id)) else {
+            return;
+        };
+
+        row.segments.retain(|segment| *segment != id);
+        let index = row
+            .segments
+            .iter()
+            .position(|segment| position(segment) > position(&id))
+            .unwrap_or(row.segments.len());
+        row.segments.insert(index, id);
+    }
+
     /// Compare two segment configs for equality
     fn segment_matches(&self, current: &SegmentConfig, preset: &SegmentConfig) -> bool {
         current.id == preset.id
@@ -279,7 +713,9 @@ impl Config {
             && self.color_matches(&current.colors.icon, &preset.colors.icon)
             && self.color_matches(&current.colors.text, &preset.colors.text)
             && self.color_matches(&current.colors.background, &preset.colors.background)
-            && current.styles.text_bold == preset.styles.text_bold
+            && self.color_matches(&current.colors.secondary, &preset.colors.secondary)
+            && current.styles == preset.styles
+            && current.layout == preset.layout
             && current.options == preset.options
     }
 
@@ -399,7 +835,24 @@ pub type Usage = RawUsage;
 
 #[derive(Deserialize)]
 pub struct Message {
+    pub id: Option<String>,
+    pub model: Option<String>,
     pub usage: Option<Usage>,
+    pub content: Option<MessageContent>,
+}
+
+/// User messages carry plain text, assistant messages a list of typed blocks
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum MessageContent {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+/// Only the block type is needed, the payload is skipped
+#[derive(Deserialize)]
+pub struct ContentBlock {
+    pub r#type: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -412,4 +865,65 @@ pub struct TranscriptEntry {
     #[serde(rename = "parentUuid")]
     pub parent_uuid: Option<String>,
     pub summary: Option<String>,
+    pub timestamp: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Segments of the first line in the order the renderer draws them
+    fn first_line(config: &Config) -> Vec<SegmentId> {
+        let mut line = config
+            .rows
+            .first()
+            .map(|row| row.segments.clone())
+            .unwrap_or_default();
+        line.extend(
+            config
+                .segments
+                .iter()
+                .map(|segment| segment.id)
+                .filter(|id| !config.rows.iter().any(|row| row.segments.contains(id))),
+        );
+        line
+    }
+
+    #[test]
+    fn set_row_back_to_first_line_keeps_its_order() {
+        let mut config = Config::default();
+        let ids: Vec<SegmentId> = config.segments.iter().map(|s| s.id).collect();
+        let (second, third) = (ids[1], ids[2]);
+
+        config.set_row(second, 1);
+        config.set_row(third, 2);
+        assert_eq!(config.rows.len(), 3);
+        assert_eq!(config.row_of(third), 2);
+
+        // Cycling past the last line wraps back to the first
+        config.set_row(third, 0);
+        let expected: Vec<SegmentId> = ids.iter().copied().filter(|id| *id != second).collect();
+        assert_eq!(first_line(&config), expected);
+        assert_eq!(config.rows.len(), 2);
+
+        config.set_row(second, 0);
+        assert!(config.rows.is_empty());
+        assert_eq!(first_line(&config), ids);
+    }
+
+    #[test]
+    fn place_in_row_moves_only_the_relocated_segment() {
+        let mut config = Config::default();
+        let ids: Vec<SegmentId> = config.segments.iter().map(|s| s.id).collect();
+        // A hand-written order that differs from the segment list
+        config.rows = vec![RowConfig {
+            segments: vec![ids[2], ids[0], ids[3]],
+            separator: None,
+        }];
+
+        // Moving the last segment up only moves it; the rest keep their hand-written order
+        config.segments.swap(2, 3);
+        config.place_in_row(ids[3]);
+        assert_eq!(config.rows[0].segments, vec![ids[3], ids[2], ids[0]]);
+    }
 }
@@ -1,4 +1,6 @@
+pub mod output;
 pub mod segments;
 pub mod statusline;
 
+pub use output::OutputFormat;
 pub use statusline::{collect_all_segments, StatusLineGenerator};
@@ -0,0 +1,592 @@
+//! Output formats for the rendered statusline. Lines are rendered with ANSI
+//! SGR sequences first and converted by the backend of the selected format.
+
+use crate::config::{AnsiColor, UnderlineStyle};
+use crate::utils::color::{strip_ansi, to_hex};
+
+/// Format the statusline is printed in
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OutputFormat {
+    /// ANSI escape sequences for terminals
+    #[default]
+    Ansi,
+    /// Text without any styling
+    Plain,
+    /// Segments with their data and resolved colors
+    Json,
+    /// tmux `#[fg=…,bg=…]` markup for `status-left`/`status-right`
+    Tmux,
+    /// ANSI sequences wrapped in `%{…%}` for zsh prompts
+    Zsh,
+    /// ANSI sequences wrapped in `\[…\]` for bash prompts
+    Bash,
+    /// `<span>` elements with inline styles inside a `<pre>` block
+    Html,
+}
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "plain" => OutputFormat::Plain,
+            "json" => OutputFormat::Json,
+            "tmux" => OutputFormat::Tmux,
+            "zsh" => OutputFormat::Zsh,
+            "bash" => OutputFormat::Bash,
+            "html" => OutputFormat::Html,
+            _ => OutputFormat::Ansi,
+        }
+    }
+
+    /// Whether the output is written to a terminal, so color support should be detected
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Ansi | OutputFormat::Zsh | OutputFormat::Bash
+        )
+    }
+
+    /// Line-based backend of the format; JSON is built from segment data instead
+    pub fn backend(&self) -> Option<Box<dyn OutputBackend>> {
+        match self {
+            OutputFormat::Ansi => Some(Box::new(AnsiBackend)),
+            OutputFormat::Plain => Some(Box::new(PlainBackend)),
+            OutputFormat::Json => None,
+            OutputFormat::Tmux => Some(Box::new(TmuxBackend)),
+            OutputFormat::Zsh => Some(Box::new(PromptBackend { zsh: true })),
+            OutputFormat::Bash => Some(Box::new(PromptBackend { zsh: false })),
+            OutputFormat::Html => Some(Box::new(HtmlBackend)),
+        }
+    }
+}
+
+/// Converts lines rendered with ANSI SGR sequences into an output format
+pub trait OutputBackend {
+    fn line(&self, ansi: &str) -> String;
+
+    /// Combine the converted lines into the printed output
+    fn document(&self, lines: Vec<String>) -> String {
+        lines.join("\n")
+    }
+}
+
+pub struct AnsiBackend;
+
+impl OutputBackend for AnsiBackend {
+    fn line(&self, ansi: &str) -> String {
+        ansi.to_string()
+    }
+}
+
+pub struct PlainBackend;
+
+impl OutputBackend for PlainBackend {
+    fn line(&self, ansi: &str) -> String {
+        strip_ansi(ansi)
+    }
+}
+
+/// Keeps the ANSI sequences and marks them as zero-width so the shell can
+/// measure the prompt
+pub struct PromptBackend {
+    zsh: bool,
+}
+
+impl OutputBackend for PromptBackend {
+    fn line(&self, ansi: &str) -> String {
+        let mut output = String::new();
+        for part in split_escapes(ansi) {
+            match part {
+                Part::Escape(sequence) if self.zsh => {
+                    output.push_str(&format!("%{{{}%}}", sequence))
+                }
+                Part::Escape(sequence) => output.push_str(&format!("\\[{}\\]", sequence)),
+                Part::Text(text) if self.zsh => output.push_str(&text.replace('%', "%%")),
+                Part::Text(text) => {
+                    for ch in text.chars() {
+                        if matches!(ch, '\\' | '$' | '`') {
+                            output.push('\\');
+                        }
+                        output.push(ch);
+                    }
+                }
+            }
+        }
+        output
+    }
+}
+
+pub struct TmuxBackend;
+
+impl TmuxBackend {
+    fn color(color: &AnsiColor) -> String {
+        match color {
+            AnsiColor::Color16 { c16 } => format!("colour{}", c16),
+            AnsiColor::Color256 { c256 } => format!("colour{}", c256),
+            AnsiColor::Rgb { .. } => to_hex(color),
+        }
+    }
+}
+
+impl OutputBackend for TmuxBackend {
+    fn line(&self, ansi: &str) -> String {
+        let mut output = String::new();
+        for run in styled_runs(ansi) {
+            let text = run.text.replace('#', "##");
+            let style = &run.style;
+
+            let mut options = Vec::new();
+            if let Some(fg) = &style.fg {
+                options.push(format!("fg={}", Self::color(fg)));
+            }
+            if let Some(bg) = &style.bg {
+                options.push(format!("bg={}", Self::color(bg)));
+            }
+            let flags = [
+                (style.bold, "bold"),
+                (style.dim, "dim"),
+                (style.italic, "italics"),
+                (style.strikethrough, "strikethrough"),
+                (style.reverse, "reverse"),
+            ];
+            options.extend(
+                flags
+                    .iter()
+                    .filter(|(set, _)| *set)
+                    .map(|(_, name)| name.to_string()),
+            );
+            if let Some(underline) = style.underline {
+                let name = match underline {
+                    UnderlineStyle::Straight => "underscore",
+                    UnderlineStyle::Double => "double-underscore",
+                    UnderlineStyle::Curly => "curly-underscore",
+                    UnderlineStyle::Dotted => "dotted-underscore",
+                    UnderlineStyle::Dashed => "dashed-underscore",
+                };
+                options.push(name.to_string());
+                if let Some(color) = &style.underline_color {
+                    options.push(format!("us={}", Self::color(color)));
+                }
+            }
+
+            if options.is_empty() {
+                output.push_str(&text);
+            } else {
+                output.push_str(&format!("#[{}]{}#[default]", options.join(","), text));
+            }
+        }
+        output
+    }
+}
+
+pub struct HtmlBackend;
+
+impl HtmlBackend {
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+}
+
+impl OutputBackend for HtmlBackend {
+    fn line(&self, ansi: &str) -> String {
+        let mut output = String::new();
+        for run in styled_runs(ansi) {
+            let text = Self::escape(&run.text);
+            let style = &run.style;
+
+            // Reverse video swaps whichever colors are known
+            let (fg, bg) = if style.reverse {
+                (&style.bg, &style.fg)
+            } else {
+                (&style.fg, &style.bg)
+            };
+
+            let mut properties = Vec::new();
+            if let Some(fg) = fg {
+                properties.push(format!("color:{}", to_hex(fg)));
+            }
+            if let Some(bg) = bg {
+                properties.push(format!("background-color:{}", to_hex(bg)));
+            }
+            if style.bold {
+                properties.push("font-weight:bold".to_string());
+            }
+            if style.dim {
+                properties.push("opacity:0.7".to_string());
+            }
+            if style.italic {
+                properties.push("font-style:italic".to_string());
+            }
+
+            let mut decorations = Vec::new();
+            if style.underline.is_some() {
+                decorations.push("underline");
+            }
+            if style.strikethrough {
+                decorations.push("line-through");
+            }
+            if !decorations.is_empty() {
+                properties.push(format!("text-decoration:{}", decorations.join(" ")));
+            }
+            let decoration_style = match style.underline {
+                Some(UnderlineStyle::Double) => Some("double"),
+                Some(UnderlineStyle::Curly) => Some("wavy"),
+                Some(UnderlineStyle::Dotted) => Some("dotted"),
+                Some(UnderlineStyle::Dashed) => Some("dashed"),
+                Some(UnderlineStyle::Straight) | None => None,
+            };
+            if let Some(decoration_style) = decoration_style {
+                properties.push(format!("text-decoration-style:{}", decoration_style));
+            }
+            if let Some(color) = style
+                .underline_color
+                .as_ref()
+                .filter(|_| style.underline.is_some())
+            {
+                properties.push(format!("text-decoration-color:{}", to_hex(color)));
+            }
+
+            if properties.is_empty() {
+                output.push_str(&text);
+            } else {
+                output.push_str(&format!(
+                    "<span style=\"{}\">{}</span>",
+                    properties.join(";"),
+                    text
+                ));
+            }
+        }
+        output
+    }
+
+    fn document(&self, lines: Vec<String>) -> String {
+        format!("<pre class=\"ccline\">{}</pre>", lines.join("\n"))
+    }
+}
+
+/// Text or an escape sequence of a rendered line
+enum Part<'a> {
+    Text(&'a str),
+    Escape(&'a str),
+}
+
+/// Split a line into escape sequences and the text between them
+fn split_escapes(line: &str) -> Vec<Part<'_>> {
+    let mut parts = Vec::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find('\x1b') {
+        if start > 0 {
+            parts.push(Part::Text(&rest[..start]));
+        }
+        // CSI sequences end with a letter, e.g. `\x1b[1;31m`
+        let end = rest[start + 1..]
+            .find(|ch: char| ch.is_ascii_alphabetic())
+            .map(|offset| start + 1 + offset + 1)
+            .unwrap_or(rest.len());
+        parts.push(Part::Escape(&rest[start..end]));
+        rest = &rest[end..];
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest));
+    }
+    parts
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct RunStyle {
+    fg: Option<AnsiColor>,
+    bg: Option<AnsiColor>,
+    underline_color: Option<AnsiColor>,
+    underline: Option<UnderlineStyle>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    strikethrough: bool,
+    reverse: bool,
+}
+
+/// Text drawn with one style
+struct StyledRun {
+    text: String,
+    style: RunStyle,
+}
+
+/// Replay the SGR sequences of a line into runs of equally styled text
+fn styled_runs(line: &str) -> Vec<StyledRun> {
+    let mut runs: Vec<StyledRun> = Vec::new();
+    let mut style = RunStyle::default();
+
+    for part in split_escapes(line) {
+        match part {
+            Part::Escape(sequence) => {
+                if let Some(params) = sequence
+                    .strip_prefix("\x1b[")
+                    .and_then(|params| params.strip_suffix('m'))
+                {
+                    apply_sgr(&mut style, params);
+                }
+            }
+            Part::Text(text) => match runs.last_mut() {
+                Some(run) if run.style == style => run.text.push_str(text),
+                _ => runs.push(StyledRun {
+                    text: text.to_string(),
+                    style: style.clone(),
+                }),
+            },
+        }
+    }
+    runs
+}
+
+/// Update a style with the parameters of one SGR sequence
+fn apply_sgr(style: &mut RunStyle, params: &str) {
+    if params.is_empty() {
+        *style = RunStyle::default();
+        return;
+    }
+
+    let mut params = params.split(';');
+    while let Some(param) = params.next() {
+        // Colon-separated parameters carry their arguments, e.g. `4:3` or `58:5:196`
+        let mut sub = param.split(':');
+        let code: u8 = sub.next().and_then(|code| code.parse().ok()).unwrap_or(0);
+        let sub: Vec<&str> = sub.collect();
+
+        match code {
+            0 => *style = RunStyle::default(),
+            1 => style.bold = true,
+            2 => style.dim = true,
+            3 => style.italic = true,
+            4 => {
+                style.underline = match sub.first().copied() {
+                    Some("0") => None,
+                    Some("2") => Some(UnderlineStyle::Double),
+                    Some("3") => Some(UnderlineStyle::Curly),
+                    Some("4") => Some(UnderlineStyle::Dotted),
+                    Some("5") => Some(UnderlineStyle::Dashed),
+                    _ => Some(UnderlineStyle::Straight),
+                }
+            }
+            7 => style.reverse = true,
+            9 => style.strikethrough = true,
+            22 => {
+                style.bold = false;
+                style.dim = false;
+            }
+            23 => style.italic = false,
+            24 => style.underline = None,
+            27 => style.reverse = false,
+            29 => style.strikethrough = false,
+            30..=37 => style.fg = Some(AnsiColor::Color16 { c16: code - 30 }),
+            90..=97 => style.fg = Some(AnsiColor::Color16 { c16: code - 90 + 8 }),
+            39 => style.fg = None,
+            40..=47 => style.bg = Some(AnsiColor::Color16 { c16: code - 40 }),
+            100..=107 => {
+                style.bg = Some(AnsiColor::Color16 {
+                    c16: code - 100 + 8,
+                })
+            }
+            49 => style.bg = None,
+            59 => style.underline_color = None,
+            38 | 48 | 58 => {
+                let color = if sub.is_empty() {
+                    extended_color(&mut params.by_ref().map(str::to_string))
+                } else {
+                    // `2::r:g:b` leaves the color space id empty
+                    let args: Vec<&str> = match sub.as_slice() {
+                        ["2", "", rest @ ..] => {
+                            std::iter::once("2").chain(rest.iter().copied()).collect()
+                        }
+                        args => args.to_vec(),
+                    };
+                    extended_color(&mut args.into_iter().map(str::to_string))
+                };
+                match code {
+                    38 => style.fg = color,
+                    48 => style.bg = color,
+                    _ => style.underline_color = color,
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Read the `5;n` or `2;r;g;b` arguments of an extended color
+fn extended_color(args: &mut dyn Iterator<Item = String>) -> Option<AnsiColor> {
+    let mut next = || args.next()?.parse::<u8>().ok();
+    match next()? {
+        5 => Some(AnsiColor::Color256 { c256: next()? }),
+        2 => Some(AnsiColor::Rgb {
+            r: next()?,
+            g: next()?,
+            b: next()?,
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sgr(params: &str) -> RunStyle {
+        let mut style = RunStyle::default();
+        apply_sgr(&mut style, params);
+        style
+    }
+
+    #[test]
+    fn apply_sgr_sets_and_resets_attributes() {
+        let style = sgr("1;2;3;7;9");
+        assert!(style.bold && style.dim && style.italic && style.reverse && style.strikethrough);
+
+        let mut style = sgr("1;2;3");
+        apply_sgr(&mut style, "22;23");
+        assert_eq!(style, RunStyle::default());
+
+        let mut style = sgr("1;31");
+        apply_sgr(&mut style, "");
+        assert_eq!(style, RunStyle::default());
+        let mut style = sgr("1;31");
+        apply_sgr(&mut style, "0");
+        assert_eq!(style, RunStyle::default());
+    }
+
+    #[test]
+    fn apply_sgr_reads_basic_colors() {
+        let style = sgr("31;42");
+        assert_eq!(style.fg, Some(AnsiColor::Color16 { c16: 1 }));
+        assert_eq!(style.bg, Some(AnsiColor::Color16 { c16: 2 }));
+
+        let mut style = sgr("96;104");
+        assert_eq!(style.fg, Some(AnsiColor::Color16 { c16: 14 }));
+        assert_eq!(style.bg, Some(AnsiColor::Color16 { c16: 12 }));
+        apply_sgr(&mut style, "39;49");
+        assert_eq!(style, RunStyle::default());
+    }
+
+    #[test]
+    fn apply_sgr_reads_extended_colors() {
+        let style = sgr("38;5;208;48;2;1;2;3");
+        assert_eq!(style.fg, Some(AnsiColor::Color256 { c256: 208 }));
+        assert_eq!(style.bg, Some(AnsiColor::Rgb { r: 1, g: 2, b: 3 }));
+        // Attributes after an extended color are still applied
+        assert!(sgr("38;5;208;1").bold);
+
+        let style = sgr("38:5:208;48:2::1:2:3");
+        assert_eq!(style.fg, Some(AnsiColor::Color256 { c256: 208 }));
+        assert_eq!(style.bg, Some(AnsiColor::Rgb { r: 1, g: 2, b: 3 }));
+
+        assert_eq!(sgr("38;5").fg, None);
+    }
+
+    #[test]
+    fn apply_sgr_reads_underline_styles_and_colors() {
+        assert_eq!(sgr("4").underline, Some(UnderlineStyle::Straight));
+        assert_eq!(sgr("4:2").underline, Some(UnderlineStyle::Double));
+        assert_eq!(sgr("4:3").underline, Some(UnderlineStyle::Curly));
+        assert_eq!(sgr("4:4").underline, Some(UnderlineStyle::Dotted));
+        assert_eq!(sgr("4:5").underline, Some(UnderlineStyle::Dashed));
+        assert_eq!(sgr("4:3;4:0").underline, None);
+        assert_eq!(sgr("4;24").underline, None);
+
+        let style = sgr("58:5:196");
+        assert_eq!(
+            style.underline_color,
+            Some(AnsiColor::Color256 { c256: 196 })
+        );
+        let mut style = sgr("58:2::255:0:0");
+        assert_eq!(
+            style.underline_color,
+            Some(AnsiColor::Rgb { r: 255, g: 0, b: 0 })
+        );
+        apply_sgr(&mut style, "59");
+        assert_eq!(style.underline_color, None);
+    }
+
+    #[test]
+    fn styled_runs_merge_equally_styled_text() {
+        let runs = styled_runs("\x1b[31mre\x1b[0m\x1b[31md\x1b[0m plain \x1b[1mbold\x1b[0m");
+        let texts: Vec<&str> = runs.iter().map(|run| run.text.as_str()).collect();
+        assert_eq!(texts, vec!["red", " plain ", "bold"]);
+        assert_eq!(runs[0].style.fg, Some(AnsiColor::Color16 { c16: 1 }));
+        assert_eq!(runs[1].style, RunStyle::default());
+        assert!(runs[2].style.bold);
+    }
+
+    #[test]
+    fn styled_runs_ignore_non_sgr_sequences() {
+        let runs = styled_runs("\x1b[2Ka\x1b[1mb");
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].text, "a");
+        assert_eq!(runs[0].style, RunStyle::default());
+    }
+
+    #[test]
+    fn plain_backend_strips_styling() {
+        assert_eq!(PlainBackend.line("\x1b[1;31mred\x1b[0m"), "red");
+    }
+
+    #[test]
+    fn zsh_backend_wraps_escapes_and_escapes_percent() {
+        let backend = PromptBackend { zsh: true };
+        assert_eq!(
+            backend.line("\x1b[31m50%\x1b[0m"),
+            "%{\x1b[31m%}50%%%{\x1b[0m%}"
+        );
+    }
+
+    #[test]
+    fn bash_backend_wraps_escapes_and_escapes_expansions() {
+        let backend = PromptBackend { zsh: false };
+        assert_eq!(
+            backend.line("\x1b[31m$HOME `x` \\\x1b[0m"),
+            "\\[\x1b[31m\\]\\$HOME \\`x\\` \\\\\\[\x1b[0m\\]"
+        );
+    }
+
+    #[test]
+    fn tmux_backend_converts_styles_and_escapes_hash() {
+        assert_eq!(
+            TmuxBackend.line("\x1b[1;38;5;208;48;2;1;2;3m#1\x1b[0m issue"),
+            "#[fg=colour208,bg=#010203,bold]##1#[default] issue"
+        );
+        assert_eq!(
+            TmuxBackend.line("\x1b[4:3m\x1b[58:5:196mx\x1b[0m"),
+            "#[curly-underscore,us=colour196]x#[default]"
+        );
+    }
+
+    #[test]
+    fn html_backend_converts_styles_and_escapes_markup() {
+        assert_eq!(
+            HtmlBackend.line("\x1b[1;31m<a & \"b\">\x1b[0m"),
+            "<span style=\"color:#cd0000;font-weight:bold\">&lt;a &amp; &quot;b&quot;&gt;</span>"
+        );
+        assert_eq!(
+            HtmlBackend.line("\x1b[7;31;42mx\x1b[0m"),
+            "<span style=\"color:#00cd00;background-color:#cd0000\">x</span>"
+        );
+        assert_eq!(
+            HtmlBackend.line("\x1b[4:3;9m\x1b[58:5:196mx\x1b[0m"),
+            "<span style=\"text-decoration:underline line-through;\
+             text-decoration-style:wavy;text-decoration-color:#ff0000\">x</span>"
+        );
+        assert_eq!(
+            HtmlBackend.document(vec!["a".to_string(), "b".to_string()]),
+            "<pre class=\"ccline\">a\nb</pre>"
+        );
+    }
+
+    #[test]
+    fn format_names_are_case_insensitive() {
+        assert_eq!(OutputFormat::from_name("JSON"), OutputFormat::Json);
+        assert_eq!(OutputFormat::from_name("tmux"), OutputFormat::Tmux);
+        assert_eq!(OutputFormat::from_name("unknown"), OutputFormat::Ansi);
+        assert!(OutputFormat::Json.backend().is_none());
+    }
+}
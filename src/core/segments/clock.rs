@@ -0,0 +1,147 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::transcript;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{FixedOffset, Local, Utc};
+use std::collections::HashMap;
+
+const DEFAULT_FORMAT: &str = "%H:%M";
+
+pub struct ClockSegment {
+    format: String,
+    utc_offset: Option<FixedOffset>,
+    show_clock: bool,
+    show_idle: bool,
+    idle_threshold_secs: u64,
+}
+
+impl Default for ClockSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClockSegment {
+    pub fn new() -> Self {
+        Self {
+            format: DEFAULT_FORMAT.to_string(),
+            utc_offset: None,
+            show_clock: true,
+            show_idle: true,
+            idle_threshold_secs: 60,
+        }
+    }
+
+    /// strftime format; invalid formats fall back to `%H:%M` instead of panicking on render
+    pub fn with_format(mut self, format: &str) -> Self {
+        let valid = !StrftimeItems::new(format).any(|item| matches!(item, Item::Error));
+        self.format = if valid { format } else { DEFAULT_FORMAT }.to_string();
+        self
+    }
+
+    /// Fixed offset such as `+09:00` or `UTC`; anything else uses the local timezone
+    pub fn with_utc_offset(mut self, utc_offset: Option<&str>) -> Self {
+        self.utc_offset = utc_offset.and_then(Self::parse_offset);
+        self
+    }
+
+    pub fn with_clock(mut self, show_clock: bool) -> Self {
+        self.show_clock = show_clock;
+        self
+    }
+
+    /// Show time since the last assistant message once it exceeds the threshold
+    pub fn with_idle(mut self, show_idle: bool, idle_threshold_secs: u64) -> Self {
+        self.show_idle = show_idle;
+        self.idle_threshold_secs = idle_threshold_secs;
+        self
+    }
+
+    fn parse_offset(offset: &str) -> Option<FixedOffset> {
+        match offset.trim() {
+            "UTC" | "utc" | "Z" => FixedOffset::east_opt(0),
+            offset => offset.parse().ok(),
+        }
+    }
+
+    fn format_idle(seconds: u64) -> String {
+        if seconds < 60 {
+            format!("{}s", seconds)
+        } else if seconds < 3600 {
+            format!("{}m", seconds / 60)
+        } else if seconds < 86_400 {
+            format!("{}h{}m", seconds / 3600, (seconds % 3600) / 60)
+        } else {
+            format!("{}d", seconds / 86_400)
+        }
+    }
+}
+
+impl Segment for ClockSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let now = Utc::now();
+        let mut metadata = HashMap::new();
+
+        let primary = if self.show_clock {
+            let (time, timezone) = match self.utc_offset {
+                Some(offset) => {
+                    let time = now.with_timezone(&offset);
+                    (time.format(&self.format).to_string(), offset.to_string())
+                }
+                None => {
+                    let time = now.with_timezone(&Local);
+                    (
+                        time.format(&self.format).to_string(),
+                        time.offset().to_string(),
+                    )
+                }
+            };
+            metadata.insert("time".to_string(), time.clone());
+            metadata.insert("timezone".to_string(), timezone);
+            time
+        } else {
+            String::new()
+        };
+
+        let mut secondary = String::new();
+        if self.show_idle {
+            if let Some(last) = transcript::last_assistant_message(&input.transcript_path) {
+                let idle_secs = (now - last).num_seconds().max(0) as u64;
+                metadata.insert("last_response_at".to_string(), last.to_rfc3339());
+                metadata.insert("idle_secs".to_string(), idle_secs.to_string());
+
+                if idle_secs >= self.idle_threshold_secs {
+                    secondary = format!("idle {}", Self::format_idle(idle_secs));
+                }
+            }
+        }
+
+        // With the clock hidden the idle time takes the primary slot
+        if primary.is_empty() {
+            if secondary.is_empty() {
+                return None;
+            }
+            return Some(SegmentData {
+                primary: secondary,
+                primary_spans: Vec::new(),
+                secondary: String::new(),
+                secondary_spans: Vec::new(),
+                overrides: Default::default(),
+                metadata,
+            });
+        }
+
+        Some(SegmentData {
+            primary,
+            primary_spans: Vec::new(),
+            secondary,
+            secondary_spans: Vec::new(),
+            overrides: Default::default(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Clock
+    }
+}
@@ -0,0 +1,114 @@
+use super::git::run_git;
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use crate::utils::transcript;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+pub struct CommitSegment {
+    show_author: bool,
+    show_session_commits: bool,
+    timeout: Option<Duration>,
+}
+
+impl Default for CommitSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommitSegment {
+    pub fn new() -> Self {
+        Self {
+            show_author: false,
+            show_session_commits: true,
+            timeout: None,
+        }
+    }
+
+    pub fn with_author(mut self, show_author: bool) -> Self {
+        self.show_author = show_author;
+        self
+    }
+
+    pub fn with_session_commits(mut self, show_session_commits: bool) -> Self {
+        self.show_session_commits = show_session_commits;
+        self
+    }
+
+    /// Limit the total time spent running git for one refresh (None = unbounded)
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn format_age(seconds: u64) -> String {
+        if seconds < 60 {
+            format!("{}s", seconds)
+        } else if seconds < 3600 {
+            format!("{}m", seconds / 60)
+        } else if seconds < 86_400 {
+            format!("{}h", seconds / 3600)
+        } else {
+            format!("{}d", seconds / 86_400)
+        }
+    }
+}
+
+impl Segment for CommitSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let working_dir = &input.workspace.current_dir;
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+
+        // Committer timestamp and author name of HEAD, separated by a unit separator
+        let log = run_git(working_dir, &["log", "-1", "--format=%ct%x1f%an"], deadline).ok()??;
+        let (timestamp, author) = log.trim().split_once('\x1f')?;
+        let committed_at: i64 = timestamp.parse().ok()?;
+        let age_secs = (Utc::now().timestamp() - committed_at).max(0) as u64;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("committed_at".to_string(), committed_at.to_string());
+        metadata.insert("age_secs".to_string(), age_secs.to_string());
+        metadata.insert("author".to_string(), author.to_string());
+
+        let mut secondary_parts = Vec::new();
+        if self.show_author && !author.is_empty() {
+            secondary_parts.push(author.to_string());
+        }
+
+        if self.show_session_commits {
+            // Commits made since the first entry of the current transcript
+            if let Some(start) = transcript::session_start(&input.transcript_path) {
+                let since = format!("--since=@{}", start.timestamp());
+                let count = run_git(
+                    working_dir,
+                    &["rev-list", "--count", &since, "HEAD"],
+                    deadline,
+                )
+                .ok()
+                .flatten()
+                .and_then(|s| s.trim().parse::<u32>().ok());
+
+                if let Some(count) = count {
+                    metadata.insert("session_start".to_string(), start.to_rfc3339());
+                    metadata.insert("session_commits".to_string(), count.to_string());
+                    secondary_parts.push(format!("{} new", count));
+                }
+            }
+        }
+
+        Some(SegmentData {
+            primary: Self::format_age(age_secs),
+            primary_spans: Vec::new(),
+            secondary: secondary_parts.join(" · "),
+            secondary_spans: Vec::new(),
+            overrides: Default::default(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Commit
+    }
+}
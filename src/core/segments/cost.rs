@@ -36,7 +36,10 @@ impl Segment for CostSegment {
 
         Some(SegmentData {
             primary,
+            primary_spans: Vec::new(),
             secondary,
+            secondary_spans: Vec::new(),
+            overrides: Default::default(),
             metadata,
         })
     }
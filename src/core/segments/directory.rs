@@ -1,13 +1,70 @@
 use super::{Segment, SegmentData};
-use crate::config::{InputData, SegmentId};
+use crate::config::{InputData, SegmentId, TerminalConfig};
+use crate::utils::width::truncate_start;
 use std::collections::HashMap;
+use std::path::Path;
+
+/// How the current directory is rendered
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DirectoryMode {
+    /// Last path component only
+    #[default]
+    Name,
+    /// Full path with the home directory shown as `~`
+    Full,
+    /// Full path with every parent abbreviated to its first character (`~/w/p/crate`)
+    Fish,
+    /// Path relative to the enclosing git repository, prefixed by the repository name
+    Repo,
+}
+
+impl DirectoryMode {
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "full" => DirectoryMode::Full,
+            "fish" => DirectoryMode::Fish,
+            "repo" => DirectoryMode::Repo,
+            _ => DirectoryMode::Name,
+        }
+    }
+}
 
 #[derive(Default)]
-pub struct DirectorySegment;
+pub struct DirectorySegment {
+    mode: DirectoryMode,
+    max_length: Option<usize>,
+    substitutions: Vec<(String, String)>,
+    terminal: TerminalConfig,
+}
 
 impl DirectorySegment {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_mode(mut self, mode: DirectoryMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Truncate the displayed path from the left to at most this many columns
+    pub fn with_max_length(mut self, max_length: Option<usize>) -> Self {
+        self.max_length = max_length.filter(|&len| len > 0);
+        self
+    }
+
+    /// Terminal settings used to measure the path for `max_length`
+    pub fn with_terminal(mut self, terminal: TerminalConfig) -> Self {
+        self.terminal = terminal;
+        self
+    }
+
+    /// Replace a leading path prefix with a short label, e.g. `/home/me/work` -> `W:`
+    pub fn with_substitutions(mut self, mut substitutions: Vec<(String, String)>) -> Self {
+        // Longest prefix wins when several substitutions match
+        substitutions.sort_by_key(|(from, _)| std::cmp::Reverse(from.len()));
+        self.substitutions = substitutions;
+        self
     }
 
     /// Extract directory name from path, handling both Unix and Windows separators
@@ -34,6 +91,96 @@ impl DirectorySegment {
             result.to_string()
         }
     }
+
+    fn separator(path: &str) -> char {
+        if path.contains('\\') && !path.contains('/') {
+            '\\'
+        } else {
+            '/'
+        }
+    }
+
+    /// Strip `prefix` from `path` only when it ends on a component boundary
+    fn strip_path_prefix<'a>(path: &'a str, prefix: &str, sep: char) -> Option<&'a str> {
+        let prefix = prefix.trim_end_matches(['/', '\\']);
+        if prefix.is_empty() {
+            return None;
+        }
+        let rest = path.strip_prefix(prefix)?;
+        (rest.is_empty() || rest.starts_with(sep)).then_some(rest)
+    }
+
+    /// Split a path into a display label (substitution or `~`) and the remaining path
+    fn split_prefix<'a>(&self, path: &'a str, sep: char) -> (String, &'a str) {
+        for (from, to) in &self.substitutions {
+            if let Some(rest) = Self::strip_path_prefix(path, from, sep) {
+                return (to.clone(), rest);
+            }
+        }
+
+        if let Some(home) = dirs::home_dir() {
+            if let Some(rest) = Self::strip_path_prefix(path, &home.to_string_lossy(), sep) {
+                return ("~".to_string(), rest);
+            }
+        }
+
+        (String::new(), path)
+    }
+
+    /// Abbreviate every component but the last to its first character, keeping leading dots
+    fn abbreviate(rest: &str, sep: char) -> String {
+        let parts: Vec<&str> = rest.split(sep).collect();
+        let last = parts.iter().rposition(|part| !part.is_empty());
+
+        parts
+            .iter()
+            .enumerate()
+            .map(|(i, part)| {
+                if Some(i) == last || part.is_empty() {
+                    part.to_string()
+                } else {
+                    let dots = part.chars().take_while(|&c| c == '.').count();
+                    part.chars().take(dots + 1).collect()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(&sep.to_string())
+    }
+
+    /// Nearest ancestor containing a `.git` entry (directory or worktree file)
+    fn find_repo_root(path: &str) -> Option<&Path> {
+        Path::new(path)
+            .ancestors()
+            .find(|dir| dir.join(".git").exists())
+    }
+
+    fn format_path(&self, path: &str) -> String {
+        let sep = Self::separator(path);
+
+        match self.mode {
+            DirectoryMode::Name => Self::extract_directory_name(path),
+            DirectoryMode::Full => {
+                let (prefix, rest) = self.split_prefix(path, sep);
+                format!("{}{}", prefix, rest)
+            }
+            DirectoryMode::Fish => {
+                let (prefix, rest) = self.split_prefix(path, sep);
+                format!("{}{}", prefix, Self::abbreviate(rest, sep))
+            }
+            DirectoryMode::Repo => match Self::find_repo_root(path) {
+                Some(root) => {
+                    let root = root.to_string_lossy();
+                    let rest = Self::strip_path_prefix(path, &root, sep).unwrap_or("");
+                    format!("{}{}", Self::extract_directory_name(&root), rest)
+                }
+                // Outside a repository the full path is the most useful fallback
+                None => {
+                    let (prefix, rest) = self.split_prefix(path, sep);
+                    format!("{}{}", prefix, rest)
+                }
+            },
+        }
+    }
 }
 
 impl Segment for DirectorySegment {
@@ -41,15 +188,27 @@ impl Segment for DirectorySegment {
         let current_dir = &input.workspace.current_dir;
 
         // Handle cross-platform path separators manually for better compatibility
-        let dir_name = Self::extract_directory_name(current_dir);
+        let mut display = self.format_path(current_dir);
+        if let Some(max_length) = self.max_length {
+            // Keep the tail of the path, which is the part that changes most often
+            display = truncate_start(&display, max_length, &self.terminal);
+        }
 
         // Store the full path in metadata for potential use
         let mut metadata = HashMap::new();
         metadata.insert("full_path".to_string(), current_dir.clone());
+        if self.mode == DirectoryMode::Repo {
+            if let Some(root) = Self::find_repo_root(current_dir) {
+                metadata.insert("repo_root".to_string(), root.to_string_lossy().to_string());
+            }
+        }
 
         Some(SegmentData {
-            primary: dir_name,
+            primary: display,
+            primary_spans: Vec::new(),
             secondary: String::new(),
+            secondary_spans: Vec::new(),
+            overrides: Default::default(),
             metadata,
         })
     }
@@ -0,0 +1,180 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+pub struct EnvironmentSegment {
+    show_venv: bool,
+    show_conda: bool,
+    show_container: bool,
+    show_ssh: bool,
+    show_nix: bool,
+}
+
+impl Default for EnvironmentSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EnvironmentSegment {
+    pub fn new() -> Self {
+        Self {
+            show_venv: true,
+            show_conda: true,
+            show_container: true,
+            show_ssh: true,
+            show_nix: true,
+        }
+    }
+
+    pub fn with_venv(mut self, show_venv: bool) -> Self {
+        self.show_venv = show_venv;
+        self
+    }
+
+    pub fn with_conda(mut self, show_conda: bool) -> Self {
+        self.show_conda = show_conda;
+        self
+    }
+
+    pub fn with_container(mut self, show_container: bool) -> Self {
+        self.show_container = show_container;
+        self
+    }
+
+    pub fn with_ssh(mut self, show_ssh: bool) -> Self {
+        self.show_ssh = show_ssh;
+        self
+    }
+
+    pub fn with_nix(mut self, show_nix: bool) -> Self {
+        self.show_nix = show_nix;
+        self
+    }
+
+    fn env_var(name: &str) -> Option<String> {
+        env::var(name).ok().filter(|value| !value.is_empty())
+    }
+
+    /// Name of the active Python virtualenv; generic `.venv`/`venv` dirs use the project name
+    fn detect_venv() -> Option<String> {
+        let venv = Self::env_var("VIRTUAL_ENV")?;
+        let path = Path::new(&venv);
+        let name = path.file_name()?.to_string_lossy().to_string();
+        if matches!(name.as_str(), ".venv" | "venv" | "env" | ".env") {
+            if let Some(parent) = path.parent().and_then(|p| p.file_name()) {
+                return Some(parent.to_string_lossy().to_string());
+            }
+        }
+        Some(name)
+    }
+
+    fn detect_conda() -> Option<String> {
+        Self::env_var("CONDA_DEFAULT_ENV")
+    }
+
+    /// Container runtime, from env markers first and then filesystem and cgroup markers
+    fn detect_container() -> Option<String> {
+        if Self::env_var("CODESPACES").is_some() {
+            return Some("codespace".to_string());
+        }
+        if Self::env_var("REMOTE_CONTAINERS").is_some() {
+            return Some("devcontainer".to_string());
+        }
+        if Self::env_var("KUBERNETES_SERVICE_HOST").is_some() {
+            return Some("k8s".to_string());
+        }
+        if Path::new("/.dockerenv").exists() {
+            return Some("docker".to_string());
+        }
+        if Path::new("/run/.containerenv").exists() {
+            return Some("podman".to_string());
+        }
+        // Set by systemd-nspawn, podman and other OCI runtimes
+        if let Some(container) = Self::env_var("container") {
+            return Some(container);
+        }
+
+        let cgroup = fs::read_to_string("/proc/1/cgroup").ok()?;
+        ["docker", "kubepods", "containerd", "lxc"]
+            .into_iter()
+            .find(|marker| cgroup.contains(marker))
+            .map(|marker| match marker {
+                "kubepods" => "k8s".to_string(),
+                other => other.to_string(),
+            })
+    }
+
+    fn detect_ssh() -> bool {
+        Self::env_var("SSH_CONNECTION").is_some() || Self::env_var("SSH_TTY").is_some()
+    }
+
+    /// `pure` or `impure` for nix-shell, `impure` is also what `nix develop` sets
+    fn detect_nix() -> Option<String> {
+        Self::env_var("IN_NIX_SHELL")
+    }
+}
+
+impl Segment for EnvironmentSegment {
+    fn collect(&self, _input: &InputData) -> Option<SegmentData> {
+        let mut badges = Vec::new();
+        let mut metadata = HashMap::new();
+
+        if self.show_venv {
+            if let Some(venv) = Self::detect_venv() {
+                badges.push(format!("venv:{}", venv));
+                metadata.insert("venv".to_string(), venv);
+            }
+        }
+
+        if self.show_conda {
+            if let Some(conda) = Self::detect_conda() {
+                badges.push(format!("conda:{}", conda));
+                metadata.insert("conda".to_string(), conda);
+            }
+        }
+
+        if self.show_container {
+            if let Some(container) = Self::detect_container() {
+                badges.push(container.clone());
+                metadata.insert("container".to_string(), container);
+            }
+        }
+
+        if self.show_ssh && Self::detect_ssh() {
+            badges.push("ssh".to_string());
+            metadata.insert("ssh".to_string(), "true".to_string());
+        }
+
+        if self.show_nix {
+            if let Some(nix) = Self::detect_nix() {
+                badges.push(if nix == "pure" {
+                    "nix:pure".to_string()
+                } else {
+                    "nix".to_string()
+                });
+                metadata.insert("nix".to_string(), nix);
+            }
+        }
+
+        if badges.is_empty() {
+            return None;
+        }
+
+        Some(SegmentData {
+            primary: badges.join(" "),
+            primary_spans: Vec::new(),
+            secondary: String::new(),
+            secondary_spans: Vec::new(),
+            overrides: Default::default(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Environment
+    }
+}
@@ -1,9 +1,14 @@
-use super::{Segment, SegmentData};
-use crate::config::{InputData, SegmentId};
+use super::{Segment, SegmentData, TextSpan};
+use crate::config::{InputData, SegmentId, SpanRole};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::process::Command;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant, UNIX_EPOCH};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GitInfo {
     pub branch: String,
     pub status: GitStatus,
@@ -12,15 +17,46 @@ pub struct GitInfo {
     pub sha: Option<String>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GitStatus {
     Clean,
     Dirty,
     Conflicts,
 }
 
+#[cfg(feature = "gix")]
+mod gix_backend;
+
+/// How the Git segment reads repository state
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GitBackend {
+    /// Shell out to the `git` binary
+    #[default]
+    Cli,
+    /// Read the repository in-process with gix (requires the `gix` feature)
+    Gix,
+}
+
+impl GitBackend {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "gix" => GitBackend::Gix,
+            _ => GitBackend::Cli,
+        }
+    }
+}
+
+/// Returned when the Git segment's overall time budget is exhausted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GitTimeout;
+
 pub struct GitSegment {
     show_sha: bool,
+    backend: GitBackend,
+    timeout: Option<Duration>,
+    stale_marker: String,
+    /// Where results are cached; `~/.claude/ccline/git_cache` unless overridden
+    cache_dir: Option<PathBuf>,
 }
 
 impl Default for GitSegment {
@@ -31,7 +67,13 @@ impl Default for GitSegment {
 
 impl GitSegment {
     pub fn new() -> Self {
-        Self { show_sha: false }
+        Self {
+            show_sha: false,
+            backend: GitBackend::Cli,
+            timeout: None,
+            stale_marker: "~".to_string(),
+            cache_dir: None,
+        }
     }
 
     pub fn with_sha(mut self, show_sha: bool) -> Self {
@@ -39,147 +81,382 @@ impl GitSegment {
         self
     }
 
-    fn get_git_info(&self, working_dir: &str) -> Option<GitInfo> {
-        if !self.is_git_repository(working_dir) {
-            return None;
+    /// Select the backend; `Gix` falls back to the CLI when built without the `gix` feature
+    pub fn with_backend(mut self, backend: GitBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Limit the total time spent running git for one refresh (None = unbounded)
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Marker appended to the status when a cached result is shown after a timeout
+    pub fn with_stale_marker(mut self, marker: String) -> Self {
+        self.stale_marker = marker;
+        self
+    }
+
+    fn get_git_info(
+        &self,
+        working_dir: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Option<GitInfo>, GitTimeout> {
+        match self.backend {
+            #[cfg(feature = "gix")]
+            GitBackend::Gix => self.get_git_info_gix(working_dir, deadline),
+            _ => self.get_git_info_cli(working_dir, deadline),
+        }
+    }
+
+    /// Run the gix backend on a worker thread so the timeout applies to it as well
+    #[cfg(feature = "gix")]
+    fn get_git_info_gix(
+        &self,
+        working_dir: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Option<GitInfo>, GitTimeout> {
+        use std::sync::mpsc::{self, RecvTimeoutError};
+
+        let (tx, rx) = mpsc::channel();
+        let working_dir = working_dir.to_string();
+        let show_sha = self.show_sha;
+        thread::spawn(move || {
+            let _ = tx.send(gix_backend::get_git_info(&working_dir, show_sha));
+        });
+
+        match deadline {
+            Some(deadline) => {
+                match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(info) => Ok(info),
+                    Err(RecvTimeoutError::Timeout) => Err(GitTimeout),
+                    Err(RecvTimeoutError::Disconnected) => Ok(None),
+                }
+            }
+            None => Ok(rx.recv().ok().flatten()),
+        }
+    }
+
+    fn get_git_info_cli(
+        &self,
+        working_dir: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Option<GitInfo>, GitTimeout> {
+        if !self.is_git_repository(working_dir, deadline)? {
+            return Ok(None);
         }
 
         let branch = self
-            .get_branch(working_dir)
+            .get_branch(working_dir, deadline)?
             .unwrap_or_else(|| "detached".to_string());
-        let status = self.get_status(working_dir);
-        let (ahead, behind) = self.get_ahead_behind(working_dir);
+        let status = self.get_status(working_dir, deadline)?;
+        let (ahead, behind) = self.get_ahead_behind(working_dir, deadline)?;
         let sha = if self.show_sha {
-            self.get_sha(working_dir)
+            self.get_sha(working_dir, deadline)?
         } else {
             None
         };
 
-        Some(GitInfo {
+        Ok(Some(GitInfo {
             branch,
             status,
             ahead,
             behind,
             sha,
-        })
+        }))
     }
 
-    fn is_git_repository(&self, working_dir: &str) -> bool {
-        Command::new("git")
-            .args(["rev-parse", "--git-dir"])
-            .current_dir(working_dir)
-            .output()
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+    fn is_git_repository(
+        &self,
+        working_dir: &str,
+        deadline: Option<Instant>,
+    ) -> Result<bool, GitTimeout> {
+        Ok(run_git(working_dir, &["rev-parse", "--git-dir"], deadline)?.is_some())
     }
 
-    fn get_branch(&self, working_dir: &str) -> Option<String> {
-        if let Ok(output) = Command::new("git")
-            .args(["branch", "--show-current"])
-            .current_dir(working_dir)
-            .output()
-        {
-            if output.status.success() {
-                let branch = String::from_utf8(output.stdout).ok()?.trim().to_string();
-                if !branch.is_empty() {
-                    return Some(branch);
-                }
+    pub(crate) fn get_branch(
+        &self,
+        working_dir: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Option<String>, GitTimeout> {
+        if let Some(output) = run_git(working_dir, &["branch", "--show-current"], deadline)? {
+            let branch = output.trim().to_string();
+            if !branch.is_empty() {
+                return Ok(Some(branch));
             }
         }
 
-        if let Ok(output) = Command::new("git")
-            .args(["symbolic-ref", "--short", "HEAD"])
-            .current_dir(working_dir)
-            .output()
+        if let Some(output) = run_git(working_dir, &["symbolic-ref", "--short", "HEAD"], deadline)?
         {
-            if output.status.success() {
-                let branch = String::from_utf8(output.stdout).ok()?.trim().to_string();
-                if !branch.is_empty() {
-                    return Some(branch);
-                }
+            let branch = output.trim().to_string();
+            if !branch.is_empty() {
+                return Ok(Some(branch));
             }
         }
 
-        None
+        Ok(None)
     }
 
-    fn get_status(&self, working_dir: &str) -> GitStatus {
-        let output = Command::new("git")
-            .args(["status", "--porcelain"])
-            .current_dir(working_dir)
-            .output();
+    fn get_status(
+        &self,
+        working_dir: &str,
+        deadline: Option<Instant>,
+    ) -> Result<GitStatus, GitTimeout> {
+        let status_text = match run_git(working_dir, &["status", "--porcelain"], deadline)? {
+            Some(output) => output,
+            None => return Ok(GitStatus::Clean),
+        };
 
-        match output {
-            Ok(output) if output.status.success() => {
-                let status_text = String::from_utf8(output.stdout).unwrap_or_default();
+        if status_text.trim().is_empty() {
+            return Ok(GitStatus::Clean);
+        }
 
-                if status_text.trim().is_empty() {
-                    return GitStatus::Clean;
-                }
+        // Unmerged entries carry one of these XY codes, see git-status(1)
+        let unmerged = status_text.lines().any(|line| {
+            matches!(
+                line.get(..2),
+                Some("DD" | "AU" | "UD" | "UA" | "DU" | "AA" | "UU")
+            )
+        });
+
+        if unmerged {
+            Ok(GitStatus::Conflicts)
+        } else {
+            Ok(GitStatus::Dirty)
+        }
+    }
+
+    fn get_ahead_behind(
+        &self,
+        working_dir: &str,
+        deadline: Option<Instant>,
+    ) -> Result<(u32, u32), GitTimeout> {
+        let ahead = self.get_commit_count(working_dir, "@{u}..HEAD", deadline)?;
+        let behind = self.get_commit_count(working_dir, "HEAD..@{u}", deadline)?;
+        Ok((ahead, behind))
+    }
+
+    fn get_commit_count(
+        &self,
+        working_dir: &str,
+        range: &str,
+        deadline: Option<Instant>,
+    ) -> Result<u32, GitTimeout> {
+        let output = run_git(working_dir, &["rev-list", "--count", range], deadline)?;
+        Ok(output.and_then(|s| s.trim().parse().ok()).unwrap_or(0))
+    }
 
-                if status_text.contains("UU")
-                    || status_text.contains("AA")
-                    || status_text.contains("DD")
-                {
-                    GitStatus::Conflicts
-                } else {
-                    GitStatus::Dirty
+    fn get_sha(
+        &self,
+        working_dir: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Option<String>, GitTimeout> {
+        let output = run_git(working_dir, &["rev-parse", "--short=7", "HEAD"], deadline)?;
+        Ok(output
+            .map(|s| s.trim().to_string())
+            .filter(|sha| !sha.is_empty()))
+    }
+}
+
+/// Run a git command, killing it if the deadline passes before it exits.
+/// Returns Ok(None) when git is unavailable or exits unsuccessfully.
+pub(crate) fn run_git(
+    working_dir: &str,
+    args: &[&str],
+    deadline: Option<Instant>,
+) -> Result<Option<String>, GitTimeout> {
+    if deadline.is_some_and(|d| Instant::now() >= d) {
+        return Err(GitTimeout);
+    }
+
+    let mut child = match Command::new("git")
+        .args(args)
+        .current_dir(working_dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(_) => return Ok(None),
+    };
+
+    // Drain stdout on a separate thread so a full pipe can't stall the child
+    let mut stdout = match child.stdout.take() {
+        Some(stdout) => stdout,
+        None => return Ok(None),
+    };
+    let reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout.read_to_end(&mut buf);
+        buf
+    });
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                let buf = reader.join().unwrap_or_default();
+                if !status.success() {
+                    return Ok(None);
                 }
+                return Ok(String::from_utf8(buf).ok());
             }
-            _ => GitStatus::Clean,
+            Ok(None) => {
+                if deadline.is_some_and(|d| Instant::now() >= d) {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(GitTimeout);
+                }
+                thread::sleep(Duration::from_millis(2));
+            }
+            Err(_) => return Ok(None),
         }
     }
+}
 
-    fn get_ahead_behind(&self, working_dir: &str) -> (u32, u32) {
-        let ahead = self.get_commit_count(working_dir, "@{u}..HEAD");
-        let behind = self.get_commit_count(working_dir, "HEAD..@{u}");
-        (ahead, behind)
+/// Locate the git directory for a working directory without spawning git.
+/// Follows `.git` files used by worktrees and submodules.
+pub(crate) fn find_git_dir(working_dir: &str) -> Option<PathBuf> {
+    for dir in Path::new(working_dir).ancestors() {
+        let dot_git = dir.join(".git");
+        if dot_git.is_dir() {
+            return Some(dot_git);
+        }
+        if dot_git.is_file() {
+            let content = std::fs::read_to_string(&dot_git).ok()?;
+            let target = content.trim().strip_prefix("gitdir:")?.trim();
+            return Some(dir.join(target));
+        }
     }
+    None
+}
 
-    fn get_commit_count(&self, working_dir: &str, range: &str) -> u32 {
-        let output = Command::new("git")
-            .args(["rev-list", "--count", range])
-            .current_dir(working_dir)
-            .output();
+/// Last known Git result of one repository, persisted between refreshes
+///
+/// Each repository gets its own file named after the repository and the
+/// entry's content, so an unchanged result is recognized by a single
+/// existence check instead of reading and rewriting the cache.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct GitCache {
+    index_mtime: Option<u64>,
+    head_mtime: Option<u64>,
+    info: GitInfo,
+}
+
+impl GitCache {
+    fn default_dir() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_default()
+            .join(".claude")
+            .join("ccline")
+            .join("git_cache")
+    }
+
+    /// 64-bit FNV-1a, which unlike `DefaultHasher` is stable across Rust
+    /// releases, so cache file names survive toolchain upgrades
+    fn hash(value: &str) -> String {
+        let hash = value.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+        format!("{:016x}", hash)
+    }
+
+    fn repo_prefix(git_dir: &Path) -> String {
+        let git_dir = git_dir
+            .canonicalize()
+            .unwrap_or_else(|_| git_dir.to_path_buf());
+        format!("{}-", Self::hash(&git_dir.to_string_lossy()))
+    }
 
-        match output {
-            Ok(output) if output.status.success() => String::from_utf8(output.stdout)
+    fn mtimes(git_dir: &Path) -> (Option<u64>, Option<u64>) {
+        let mtime = |name: &str| {
+            std::fs::metadata(git_dir.join(name))
+                .and_then(|m| m.modified())
                 .ok()
-                .and_then(|s| s.trim().parse().ok())
-                .unwrap_or(0),
-            _ => 0,
-        }
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as u64)
+        };
+        (mtime("index"), mtime("HEAD"))
     }
 
-    fn get_sha(&self, working_dir: &str) -> Option<String> {
-        let output = Command::new("git")
-            .args(["rev-parse", "--short=7", "HEAD"])
-            .current_dir(working_dir)
-            .output()
-            .ok()?;
+    /// Record a fresh result, skipping all file writes when nothing changed
+    fn store(cache_dir: &Path, git_dir: &Path, info: &GitInfo) {
+        let (index_mtime, head_mtime) = Self::mtimes(git_dir);
+        let entry = GitCache {
+            index_mtime,
+            head_mtime,
+            info: info.clone(),
+        };
+        let Ok(content) = serde_json::to_string(&entry) else {
+            return;
+        };
 
-        if output.status.success() {
-            let sha = String::from_utf8(output.stdout).ok()?.trim().to_string();
-            if sha.is_empty() {
-                None
-            } else {
-                Some(sha)
+        let prefix = Self::repo_prefix(git_dir);
+        let path = cache_dir.join(format!("{}{}.json", prefix, Self::hash(&content)));
+        if path.exists() {
+            return;
+        }
+
+        // Replace the repository's previous entry
+        if let Ok(files) = std::fs::read_dir(cache_dir) {
+            for file in files.flatten() {
+                if file.file_name().to_string_lossy().starts_with(&prefix) {
+                    let _ = std::fs::remove_file(file.path());
+                }
             }
-        } else {
-            None
         }
+        let _ = std::fs::create_dir_all(cache_dir).and_then(|_| std::fs::write(path, content));
+    }
+
+    /// Last result for the repository, and whether its index and HEAD are
+    /// unchanged since it was recorded
+    fn lookup(cache_dir: &Path, git_dir: &Path) -> Option<(GitInfo, bool)> {
+        let prefix = Self::repo_prefix(git_dir);
+        let file = std::fs::read_dir(cache_dir)
+            .ok()?
+            .flatten()
+            .find(|file| file.file_name().to_string_lossy().starts_with(&prefix))?;
+        let content = std::fs::read_to_string(file.path()).ok()?;
+        let entry: GitCache = serde_json::from_str(&content).ok()?;
+
+        let fresh = (entry.index_mtime, entry.head_mtime) == Self::mtimes(git_dir);
+        Some((entry.info, fresh))
     }
 }
 
 impl Segment for GitSegment {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
-        let git_info = self.get_git_info(&input.workspace.current_dir)?;
+        let working_dir = &input.workspace.current_dir;
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let git_dir = find_git_dir(working_dir);
+
+        let cache_dir = self.cache_dir.clone().unwrap_or_else(GitCache::default_dir);
+
+        // `fresh` tells whether the index and HEAD still match the shown result
+        let (git_info, stale, fresh) = match self.get_git_info(working_dir, deadline) {
+            Ok(Some(info)) => {
+                if let Some(ref dir) = git_dir {
+                    GitCache::store(&cache_dir, dir, &info);
+                }
+                (info, false, true)
+            }
+            Ok(None) => return None,
+            // Fall back to the last result seen for this repository, even if it has changed since
+            Err(GitTimeout) => {
+                let (info, fresh) = GitCache::lookup(&cache_dir, git_dir.as_deref()?)?;
+                (info, true, fresh)
+            }
+        };
 
         let mut metadata = HashMap::new();
         metadata.insert("branch".to_string(), git_info.branch.clone());
         metadata.insert("status".to_string(), format!("{:?}", git_info.status));
         metadata.insert("ahead".to_string(), git_info.ahead.to_string());
         metadata.insert("behind".to_string(), git_info.behind.to_string());
+        metadata.insert("stale".to_string(), stale.to_string());
+        metadata.insert("cache_fresh".to_string(), fresh.to_string());
 
         if let Some(ref sha) = git_info.sha {
             metadata.insert("sha".to_string(), sha.clone());
@@ -189,30 +466,157 @@ impl Segment for GitSegment {
         let mut status_parts = Vec::new();
 
         match git_info.status {
-            GitStatus::Clean => status_parts.push("✓".to_string()),
-            GitStatus::Dirty => status_parts.push("●".to_string()),
-            GitStatus::Conflicts => status_parts.push("⚠".to_string()),
+            GitStatus::Clean => status_parts.push(TextSpan::with_role("✓", SpanRole::Value)),
+            GitStatus::Dirty => status_parts.push(TextSpan::with_role("●", SpanRole::Warning)),
+            GitStatus::Conflicts => status_parts.push(TextSpan::with_role("⚠", SpanRole::Warning)),
         }
 
         if git_info.ahead > 0 {
-            status_parts.push(format!("↑{}", git_info.ahead));
+            status_parts.push(TextSpan::with_role(
+                format!("↑{}", git_info.ahead),
+                SpanRole::Value,
+            ));
         }
         if git_info.behind > 0 {
-            status_parts.push(format!("↓{}", git_info.behind));
+            status_parts.push(TextSpan::with_role(
+                format!("↓{}", git_info.behind),
+                SpanRole::Value,
+            ));
         }
 
         if let Some(ref sha) = git_info.sha {
-            status_parts.push(sha.clone());
+            status_parts.push(TextSpan::with_role(sha.clone(), SpanRole::Muted));
+        }
+
+        if stale && !self.stale_marker.is_empty() {
+            status_parts.push(TextSpan::with_role(
+                self.stale_marker.clone(),
+                SpanRole::Muted,
+            ));
         }
 
-        Some(SegmentData {
-            primary,
-            secondary: status_parts.join(" "),
-            metadata,
-        })
+        let mut secondary_spans = Vec::new();
+        for (i, part) in status_parts.into_iter().enumerate() {
+            if i > 0 {
+                secondary_spans.push(TextSpan::plain(" "));
+            }
+            secondary_spans.push(part);
+        }
+
+        Some(
+            SegmentData {
+                primary,
+                primary_spans: Vec::new(),
+                secondary: String::new(),
+                secondary_spans: Vec::new(),
+                overrides: Default::default(),
+                metadata,
+            }
+            .with_secondary_spans(secondary_spans),
+        )
     }
 
     fn id(&self) -> SegmentId {
         SegmentId::Git
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Model, Workspace};
+    use std::fs::{self, File};
+    use std::time::SystemTime;
+
+    /// Fresh directory under the system temp dir, unique per test
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ccline-git-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn input(current_dir: &Path) -> InputData {
+        InputData {
+            model: Model {
+                id: "claude-sonnet-4".to_string(),
+                display_name: "Sonnet 4".to_string(),
+            },
+            workspace: Workspace {
+                current_dir: current_dir.to_string_lossy().to_string(),
+            },
+            transcript_path: String::new(),
+            cost: None,
+            output_style: None,
+        }
+    }
+
+    fn info() -> GitInfo {
+        GitInfo {
+            branch: "main".to_string(),
+            status: GitStatus::Dirty,
+            ahead: 1,
+            behind: 0,
+            sha: None,
+        }
+    }
+
+    #[test]
+    fn hash_is_stable_fnv1a() {
+        assert_eq!(GitCache::hash(""), "cbf29ce484222325");
+        assert_eq!(GitCache::hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn store_keeps_one_entry_per_repository() {
+        let root = temp_dir("store");
+        let (repo, cache_dir) = (root.join("repo"), root.join("cache"));
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        let git_dir = repo.join(".git");
+
+        GitCache::store(&cache_dir, &git_dir, &info());
+        GitCache::store(&cache_dir, &git_dir, &info());
+        let mut changed = info();
+        changed.status = GitStatus::Clean;
+        GitCache::store(&cache_dir, &git_dir, &changed);
+
+        assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 1);
+        assert_eq!(
+            GitCache::lookup(&cache_dir, &git_dir),
+            Some((changed, true))
+        );
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn timeout_shows_cached_result_after_index_changes() {
+        let root = temp_dir("timeout");
+        let (repo, cache_dir) = (root.join("repo"), root.join("cache"));
+        let git_dir = repo.join(".git");
+        fs::create_dir_all(&git_dir).unwrap();
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(git_dir.join("index"), "").unwrap();
+
+        GitCache::store(&cache_dir, &git_dir, &info());
+
+        // Staging a file rewrites the index
+        File::options()
+            .write(true)
+            .open(git_dir.join("index"))
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+
+        let segment = GitSegment {
+            cache_dir: Some(cache_dir),
+            ..GitSegment::new().with_timeout(Some(Duration::ZERO))
+        };
+        let data = segment.collect(&input(&repo)).expect("cached result");
+
+        assert_eq!(data.primary, "main");
+        assert_eq!(data.metadata["stale"], "true");
+        assert_eq!(data.metadata["cache_fresh"], "false");
+        assert!(data.secondary.ends_with('~'));
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,78 @@
+//! In-process git backend built on gix, used when `backend = "gix"`.
+//! Produces the same `GitInfo` as the git CLI backend without spawning processes.
+
+use super::{GitInfo, GitStatus};
+use gix::refs::FullName;
+use gix::Repository;
+
+pub(super) fn get_git_info(working_dir: &str, show_sha: bool) -> Option<GitInfo> {
+    let repo = gix::discover(working_dir).ok()?;
+
+    // Unborn branches still have a name, only a detached HEAD has none
+    let head_name = repo.head_name().ok()?;
+    let branch = head_name
+        .as_ref()
+        .map(|name| name.shorten().to_string())
+        .unwrap_or_else(|| "detached".to_string());
+
+    let status = get_status(&repo);
+    let (ahead, behind) = get_ahead_behind(&repo, head_name.as_ref()).unwrap_or((0, 0));
+    let sha = if show_sha {
+        repo.head_id()
+            .ok()
+            .map(|id| id.to_hex_with_len(7).to_string())
+    } else {
+        None
+    };
+
+    Some(GitInfo {
+        branch,
+        status,
+        ahead,
+        behind,
+        sha,
+    })
+}
+
+fn get_status(repo: &Repository) -> GitStatus {
+    let has_conflicts = repo
+        .index_or_empty()
+        .map(|index| index.entries().iter().any(|entry| entry.stage_raw() != 0))
+        .unwrap_or(false);
+    if has_conflicts {
+        return GitStatus::Conflicts;
+    }
+
+    // Any change, including untracked files, counts as dirty like `git status --porcelain`
+    let is_dirty = repo
+        .status(gix::progress::Discard)
+        .ok()
+        .and_then(|status| status.into_iter(Vec::new()).ok())
+        .is_some_and(|mut iter| matches!(iter.next(), Some(Ok(_))));
+
+    if is_dirty {
+        GitStatus::Dirty
+    } else {
+        GitStatus::Clean
+    }
+}
+
+fn get_ahead_behind(repo: &Repository, head_name: Option<&FullName>) -> Option<(u32, u32)> {
+    let tracking = repo
+        .branch_remote_tracking_ref_name(head_name?.as_ref(), gix::remote::Direction::Fetch)?
+        .ok()?;
+    let upstream = repo
+        .find_reference(tracking.as_ref())
+        .ok()?
+        .peel_to_id()
+        .ok()?
+        .detach();
+    let head = repo.head_id().ok()?.detach();
+
+    let count = |tip, hidden| -> Option<u32> {
+        let walk = repo.rev_walk([tip]).with_hidden([hidden]).all().ok()?;
+        Some(walk.filter_map(Result::ok).count() as u32)
+    };
+
+    Some((count(head, upstream)?, count(upstream, head)?))
+}
@@ -1,13 +1,21 @@
+pub mod clock;
+pub mod commit;
 pub mod cost;
 pub mod directory;
+pub mod environment;
 pub mod git;
 pub mod model;
 pub mod output_style;
+pub mod productivity;
+pub mod project;
+pub mod pull_request;
 pub mod session;
+pub mod system;
 pub mod update;
 pub mod usage;
 
-use crate::config::{InputData, SegmentId};
+use crate::config::{AnsiColor, IconConfig, InputData, ModelConfig, SegmentId, SpanRole};
+use std::cell::OnceCell;
 use std::collections::HashMap;
 
 // New Segment trait for data collection only
@@ -19,16 +27,107 @@ pub trait Segment {
 #[derive(Debug, Clone)]
 pub struct SegmentData {
     pub primary: String,
+    /// Styled pieces of `primary`, drawn in its place when present
+    pub primary_spans: Vec<TextSpan>,
     pub secondary: String,
+    /// Styled pieces of `secondary`, drawn in its place when present
+    pub secondary_spans: Vec<TextSpan>,
+    /// Styling the segment picks at runtime over its configured style
+    pub overrides: SegmentOverrides,
     pub metadata: HashMap<String, String>,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct SegmentOverrides {
+    /// More specific icon, e.g. per project type
+    pub icon: Option<IconConfig>,
+    /// Icon and text color, e.g. a provider color from models.toml
+    pub color: Option<AnsiColor>,
+    /// Threshold level that recolors the text of threshold-based segments
+    pub level: ResourceLevel,
+}
+
+impl SegmentData {
+    /// Set the primary text from styled spans, keeping `primary` as their plain text
+    pub fn with_primary_spans(mut self, spans: Vec<TextSpan>) -> Self {
+        self.primary = spans.iter().map(|span| span.text.as_str()).collect();
+        self.primary_spans = spans;
+        self
+    }
+
+    /// Set the secondary text from styled spans, keeping `secondary` as their plain text
+    pub fn with_secondary_spans(mut self, spans: Vec<TextSpan>) -> Self {
+        self.secondary = spans.iter().map(|span| span.text.as_str()).collect();
+        self.secondary_spans = spans;
+        self
+    }
+}
+
+/// A run of text with its own color, or a role the theme picks the color for;
+/// without either it uses the segment's color
+#[derive(Debug, Clone)]
+pub struct TextSpan {
+    pub text: String,
+    pub color: Option<AnsiColor>,
+    pub role: Option<SpanRole>,
+}
+
+impl TextSpan {
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            color: None,
+            role: None,
+        }
+    }
+
+    pub fn colored(text: impl Into<String>, color: AnsiColor) -> Self {
+        Self {
+            text: text.into(),
+            color: Some(color),
+            role: None,
+        }
+    }
+
+    pub fn with_role(text: impl Into<String>, role: SpanRole) -> Self {
+        Self {
+            text: text.into(),
+            color: None,
+            role: Some(role),
+        }
+    }
+}
+
+/// State shared by all segments during one statusline render.
+/// Expensive resources are loaded on first use and at most once per invocation.
+#[derive(Default)]
+pub struct SegmentContext {
+    model_config: OnceCell<ModelConfig>,
+}
+
+impl SegmentContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn model_config(&self) -> &ModelConfig {
+        self.model_config.get_or_init(ModelConfig::load)
+    }
+}
+
 // Re-export all segment types
+pub use clock::ClockSegment;
+pub use commit::CommitSegment;
 pub use cost::CostSegment;
-pub use directory::DirectorySegment;
-pub use git::GitSegment;
+pub use directory::{DirectoryMode, DirectorySegment};
+pub use environment::EnvironmentSegment;
+pub use git::{GitBackend, GitSegment};
 pub use model::ModelSegment;
 pub use output_style::OutputStyleSegment;
-pub use session::SessionSegment;
+pub use productivity::{ProductivityMetric, ProductivitySegment};
+pub use project::ProjectSegment;
+pub use pull_request::PullRequestSegment;
+pub use session::{SessionMode, SessionSegment};
+pub use system::{ResourceLevel, SystemSegment, Thresholds};
 pub use update::UpdateSegment;
 pub use usage::UsageSegment;
@@ -1,25 +1,136 @@
-use super::{Segment, SegmentData};
-use crate::config::{InputData, ModelConfig, SegmentId};
+use super::{Segment, SegmentData, SegmentOverrides};
+use crate::config::{
+    IconConfig, InputData, MessageContent, ModelConfig, ModelEntry, SegmentId, TranscriptEntry,
+};
+use crate::utils::transcript;
 use std::collections::HashMap;
 
-#[derive(Default)]
-pub struct ModelSegment;
+pub struct ModelSegment<'a> {
+    model_config: Option<&'a ModelConfig>,
+    show_thinking: bool,
+    show_context_variant: bool,
+    show_fallback: bool,
+    show_provider: bool,
+}
+
+impl Default for ModelSegment<'_> {
+    fn default() -> Self {
+        Self {
+            model_config: None,
+            show_thinking: false,
+            show_context_variant: false,
+            show_fallback: false,
+            show_provider: true,
+        }
+    }
+}
 
-impl ModelSegment {
+impl<'a> ModelSegment<'a> {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Show the `provider` declared in models.toml as a badge (on by default)
+    pub fn with_provider(mut self, show_provider: bool) -> Self {
+        self.show_provider = show_provider;
+        self
+    }
+
+    /// Share an already loaded model config instead of loading it again
+    pub fn with_model_config(mut self, model_config: &'a ModelConfig) -> Self {
+        self.model_config = Some(model_config);
+        self
+    }
+
+    /// Mark sessions whose latest response used extended thinking
+    pub fn with_thinking(mut self, show_thinking: bool) -> Self {
+        self.show_thinking = show_thinking;
+        self
+    }
+
+    /// Mark the 1M-context variant of a model
+    pub fn with_context_variant(mut self, show_context_variant: bool) -> Self {
+        self.show_context_variant = show_context_variant;
+        self
+    }
+
+    /// Show the served model when it differs from the requested one
+    pub fn with_fallback(mut self, show_fallback: bool) -> Self {
+        self.show_fallback = show_fallback;
+        self
     }
 }
 
-impl Segment for ModelSegment {
+impl Segment for ModelSegment<'_> {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let loaded;
+        let model_config = match self.model_config {
+            Some(model_config) => model_config,
+            None => {
+                loaded = ModelConfig::load();
+                &loaded
+            }
+        };
+
         let mut metadata = HashMap::new();
         metadata.insert("model_id".to_string(), input.model.id.clone());
         metadata.insert("display_name".to_string(), input.model.display_name.clone());
 
+        let primary =
+            self.format_model_name(model_config, &input.model.id, &input.model.display_name);
+        let mut indicators = Vec::new();
+        let mut overrides = SegmentOverrides::default();
+
+        if let Some(entry) = model_config.get_entry(&input.model.id) {
+            Self::insert_profile(entry, &mut metadata);
+            overrides.icon = entry.icon.as_ref().map(|icon| IconConfig {
+                plain: icon.clone(),
+                nerd_font: icon.clone(),
+            });
+            overrides.color = entry.color.clone();
+            if let Some(provider) = entry.provider.as_ref().filter(|_| self.show_provider) {
+                indicators.push(provider.clone());
+            }
+        }
+
+        if Self::is_1m_variant(&input.model.id, &input.model.display_name) {
+            metadata.insert("context_variant".to_string(), "1m".to_string());
+            // Configured display names often already say 1M
+            if self.show_context_variant && !primary.contains("1M") {
+                indicators.push("1M".to_string());
+            }
+        }
+
+        if self.show_thinking || self.show_fallback {
+            let response = transcript::last_assistant_response(&input.transcript_path);
+
+            if self.show_thinking && Self::is_thinking(&response) {
+                metadata.insert("thinking".to_string(), "true".to_string());
+                indicators.push("thinking".to_string());
+            }
+
+            let served = response
+                .iter()
+                .filter_map(|entry| entry.message.as_ref()?.model.as_deref())
+                .next_back();
+            if let Some(served) = served {
+                metadata.insert("served_model".to_string(), served.to_string());
+                if self.show_fallback && Self::is_fallback(&input.model.id, served) {
+                    metadata.insert("fallback".to_string(), "true".to_string());
+                    let served_name = model_config
+                        .get_display_name(served)
+                        .unwrap_or_else(|| served.to_string());
+                    indicators.push(format!("↪ {}", served_name));
+                }
+            }
+        }
+
         Some(SegmentData {
-            primary: self.format_model_name(&input.model.id, &input.model.display_name),
-            secondary: String::new(),
+            primary,
+            primary_spans: Vec::new(),
+            secondary: indicators.join(" · "),
+            secondary_spans: Vec::new(),
+            overrides,
             metadata,
         })
     }
@@ -29,10 +140,13 @@ impl Segment for ModelSegment {
     }
 }
 
-impl ModelSegment {
-    fn format_model_name(&self, id: &str, display_name: &str) -> String {
-        let model_config = ModelConfig::load();
-
+impl ModelSegment<'_> {
+    fn format_model_name(
+        &self,
+        model_config: &ModelConfig,
+        id: &str,
+        display_name: &str,
+    ) -> String {
         // Try to get display name from external config first
         if let Some(config_name) = model_config.get_display_name(id) {
             config_name
@@ -41,4 +155,64 @@ impl ModelSegment {
             display_name.to_string()
         }
     }
+
+    /// Expose the provider profile in the metadata
+    fn insert_profile(entry: &ModelEntry, metadata: &mut HashMap<String, String>) {
+        if let Some(provider) = &entry.provider {
+            metadata.insert("provider".to_string(), provider.clone());
+        }
+        metadata.insert("context_limit".to_string(), entry.context_limit.to_string());
+        if let Some(max_output) = entry.max_output_tokens {
+            metadata.insert("max_output_tokens".to_string(), max_output.to_string());
+        }
+        if let Some(pricing) = &entry.pricing {
+            metadata.insert("price_input".to_string(), pricing.input.to_string());
+            metadata.insert("price_output".to_string(), pricing.output.to_string());
+            if let Some(cache_read) = pricing.cache_read {
+                metadata.insert("price_cache_read".to_string(), cache_read.to_string());
+            }
+            if let Some(cache_write) = pricing.cache_write {
+                metadata.insert("price_cache_write".to_string(), cache_write.to_string());
+            }
+        }
+    }
+
+    /// Claude Code appends `[1m]` to the model id when the 1M-context beta is selected
+    fn is_1m_variant(id: &str, display_name: &str) -> bool {
+        id.to_lowercase().contains("[1m]") || display_name.contains("1M")
+    }
+
+    /// Extended thinking shows up as thinking blocks in the response content,
+    /// or is forced on for every request through `MAX_THINKING_TOKENS`
+    fn is_thinking(response: &[TranscriptEntry]) -> bool {
+        let env_budget = std::env::var("MAX_THINKING_TOKENS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .is_some_and(|budget| budget > 0);
+
+        let has_thinking_block =
+            |entry: &TranscriptEntry| match entry.message.as_ref().and_then(|m| m.content.as_ref())
+            {
+                Some(MessageContent::Blocks(blocks)) => blocks.iter().any(|block| {
+                    matches!(
+                        block.r#type.as_deref(),
+                        Some("thinking" | "redacted_thinking")
+                    )
+                }),
+                _ => false,
+            };
+
+        env_budget || response.iter().any(has_thinking_block)
+    }
+
+    /// The requested id may carry a variant suffix or omit the date the API reports
+    fn is_fallback(requested: &str, served: &str) -> bool {
+        if served == "<synthetic>" {
+            return false;
+        }
+        let requested = requested.to_lowercase();
+        let requested = requested.trim_end_matches("[1m]");
+        let served = served.to_lowercase();
+        !(served.starts_with(requested) || requested.starts_with(&served))
+    }
 }
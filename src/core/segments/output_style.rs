@@ -23,7 +23,10 @@ impl Segment for OutputStyleSegment {
 
         Some(SegmentData {
             primary,
+            primary_spans: Vec::new(),
             secondary: String::new(),
+            secondary_spans: Vec::new(),
+            overrides: Default::default(),
             metadata,
         })
     }
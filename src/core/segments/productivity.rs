@@ -0,0 +1,116 @@
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+
+/// A metric the productivity segment can display
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProductivityMetric {
+    /// Lines added minus lines removed
+    Net,
+    /// Changed lines per minute of session time
+    Rate,
+    /// Session cost per 100 changed lines
+    Cost,
+}
+
+impl ProductivityMetric {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "net" => Some(ProductivityMetric::Net),
+            "rate" => Some(ProductivityMetric::Rate),
+            "cost" => Some(ProductivityMetric::Cost),
+            _ => None,
+        }
+    }
+}
+
+pub struct ProductivitySegment {
+    metrics: Vec<ProductivityMetric>,
+}
+
+impl Default for ProductivitySegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProductivitySegment {
+    pub fn new() -> Self {
+        Self {
+            metrics: vec![
+                ProductivityMetric::Net,
+                ProductivityMetric::Rate,
+                ProductivityMetric::Cost,
+            ],
+        }
+    }
+
+    /// Metrics to display, in order; an empty list keeps the defaults
+    pub fn with_metrics(mut self, metrics: Vec<ProductivityMetric>) -> Self {
+        if !metrics.is_empty() {
+            self.metrics = metrics;
+        }
+        self
+    }
+}
+
+impl Segment for ProductivitySegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let cost_data = input.cost.as_ref()?;
+        let added = cost_data.total_lines_added.unwrap_or(0) as i64;
+        let removed = cost_data.total_lines_removed.unwrap_or(0) as i64;
+        if cost_data.total_lines_added.is_none() && cost_data.total_lines_removed.is_none() {
+            return None;
+        }
+
+        let net = added - removed;
+        let changed = added + removed;
+        let minutes = cost_data
+            .total_duration_ms
+            .filter(|&ms| ms > 0)
+            .map(|ms| ms as f64 / 60_000.0);
+        let lines_per_min = minutes.map(|minutes| changed as f64 / minutes);
+        let cost_per_100 = cost_data
+            .total_cost_usd
+            .filter(|_| changed > 0)
+            .map(|cost| cost / changed as f64 * 100.0);
+
+        let mut metadata = HashMap::new();
+        metadata.insert("lines_added".to_string(), added.to_string());
+        metadata.insert("lines_removed".to_string(), removed.to_string());
+        metadata.insert("net_lines".to_string(), net.to_string());
+        if let Some(rate) = lines_per_min {
+            metadata.insert("lines_per_min".to_string(), format!("{:.2}", rate));
+        }
+        if let Some(cost) = cost_per_100 {
+            metadata.insert("cost_per_100_lines".to_string(), format!("{:.4}", cost));
+        }
+
+        let parts: Vec<String> = self
+            .metrics
+            .iter()
+            .filter_map(|metric| match metric {
+                ProductivityMetric::Net => Some(format!("{:+} net", net)),
+                ProductivityMetric::Rate => lines_per_min.map(|rate| format!("{:.1}/min", rate)),
+                ProductivityMetric::Cost => cost_per_100.map(|cost| format!("${:.2}/100", cost)),
+            })
+            .collect();
+
+        if parts.is_empty() {
+            return None;
+        }
+
+        Some(SegmentData {
+            primary: parts.join(" · "),
+            primary_spans: Vec::new(),
+            secondary: String::new(),
+            secondary_spans: Vec::new(),
+            overrides: Default::default(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Productivity
+    }
+}
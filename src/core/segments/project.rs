@@ -0,0 +1,269 @@
+use super::{Segment, SegmentData, SegmentOverrides};
+use crate::config::{IconConfig, InputData, SegmentId};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Toolchain detected from marker files in the working directory
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProjectType {
+    Rust,
+    Node,
+    Python,
+    Go,
+}
+
+impl ProjectType {
+    /// Detection order, also used to pick the primary type in polyglot directories
+    pub const ALL: [ProjectType; 4] = [
+        ProjectType::Rust,
+        ProjectType::Node,
+        ProjectType::Python,
+        ProjectType::Go,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            ProjectType::Rust => "rust",
+            ProjectType::Node => "node",
+            ProjectType::Python => "python",
+            ProjectType::Go => "go",
+        }
+    }
+
+    fn markers(&self) -> &'static [&'static str] {
+        match self {
+            ProjectType::Rust => &["rust-toolchain.toml", "rust-toolchain", "Cargo.toml"],
+            ProjectType::Node => &[".nvmrc", "package.json"],
+            ProjectType::Python => &[".python-version", "pyproject.toml"],
+            ProjectType::Go => &["go.mod"],
+        }
+    }
+
+    /// Display name, e.g. in the icon selector
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProjectType::Rust => "Rust",
+            ProjectType::Node => "Node.js",
+            ProjectType::Python => "Python",
+            ProjectType::Go => "Go",
+        }
+    }
+
+    /// Plain and Nerd Font icons, also offered by the TUI icon selector
+    pub fn icons(&self) -> (&'static str, &'static str) {
+        match self {
+            ProjectType::Rust => ("🦀", "\u{e7a8}"),
+            ProjectType::Node => ("⬢", "\u{e718}"),
+            ProjectType::Python => ("🐍", "\u{e73c}"),
+            ProjectType::Go => ("🐹", "\u{e724}"),
+        }
+    }
+
+    fn detect(&self, dir: &Path) -> bool {
+        self.markers()
+            .iter()
+            .any(|marker| dir.join(marker).is_file())
+    }
+
+    /// Pinned or declared toolchain version and the file it came from
+    fn version(&self, dir: &Path) -> Option<(String, &'static str)> {
+        match self {
+            ProjectType::Rust => read_rust_version(dir),
+            ProjectType::Node => read_node_version(dir),
+            ProjectType::Python => read_python_version(dir),
+            ProjectType::Go => read_go_version(dir),
+        }
+    }
+}
+
+fn read_toml(path: &Path) -> Option<toml::Value> {
+    toml::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// First non-empty, non-comment line of a single-value version file
+fn read_version_file(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()?
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.trim_start_matches('v').to_string())
+}
+
+fn read_rust_version(dir: &Path) -> Option<(String, &'static str)> {
+    let channel = read_toml(&dir.join("rust-toolchain.toml")).and_then(|toml| {
+        toml.get("toolchain")?
+            .get("channel")?
+            .as_str()
+            .map(str::to_string)
+    });
+    if let Some(channel) = channel {
+        return Some((channel, "rust-toolchain.toml"));
+    }
+
+    // Legacy toolchain file holds just the channel name
+    if let Some(channel) = read_version_file(&dir.join("rust-toolchain")) {
+        return Some((channel, "rust-toolchain"));
+    }
+
+    let cargo = read_toml(&dir.join("Cargo.toml"))?;
+    let package = cargo.get("package").or_else(|| {
+        cargo
+            .get("workspace")
+            .and_then(|workspace| workspace.get("package"))
+    })?;
+    package
+        .get("rust-version")?
+        .as_str()
+        .map(|version| (version.to_string(), "Cargo.toml"))
+}
+
+fn read_node_version(dir: &Path) -> Option<(String, &'static str)> {
+    if let Some(version) = read_version_file(&dir.join(".nvmrc")) {
+        return Some((version, ".nvmrc"));
+    }
+
+    let content = fs::read_to_string(dir.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    package
+        .get("engines")?
+        .get("node")?
+        .as_str()
+        .map(|version| (version.to_string(), "package.json"))
+}
+
+fn read_python_version(dir: &Path) -> Option<(String, &'static str)> {
+    if let Some(version) = read_version_file(&dir.join(".python-version")) {
+        return Some((version, ".python-version"));
+    }
+
+    let pyproject = read_toml(&dir.join("pyproject.toml"))?;
+    let requires = pyproject
+        .get("project")
+        .and_then(|project| project.get("requires-python"))
+        .or_else(|| {
+            // Poetry declares the interpreter as a dependency
+            pyproject
+                .get("tool")?
+                .get("poetry")?
+                .get("dependencies")?
+                .get("python")
+        })?;
+    requires
+        .as_str()
+        .map(|version| (version.to_string(), "pyproject.toml"))
+}
+
+fn read_go_version(dir: &Path) -> Option<(String, &'static str)> {
+    let content = fs::read_to_string(dir.join("go.mod")).ok()?;
+    let mut go_version = None;
+    for line in content.lines().map(str::trim) {
+        // An explicit toolchain directive pins more precisely than the language version
+        if let Some(toolchain) = line.strip_prefix("toolchain go") {
+            return Some((toolchain.trim().to_string(), "go.mod"));
+        }
+        if let Some(version) = line.strip_prefix("go ") {
+            go_version = Some(version.trim().to_string());
+        }
+    }
+    go_version.map(|version| (version, "go.mod"))
+}
+
+pub struct ProjectSegment {
+    show_version: bool,
+    type_icons: bool,
+}
+
+impl Default for ProjectSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProjectSegment {
+    pub fn new() -> Self {
+        Self {
+            show_version: true,
+            type_icons: true,
+        }
+    }
+
+    pub fn with_version(mut self, show_version: bool) -> Self {
+        self.show_version = show_version;
+        self
+    }
+
+    /// Replace the configured icon with one matching the detected project type
+    pub fn with_type_icons(mut self, type_icons: bool) -> Self {
+        self.type_icons = type_icons;
+        self
+    }
+
+    /// Nearest directory, starting at the working directory, that contains any marker file
+    fn find_project(working_dir: &str) -> Option<(&Path, Vec<ProjectType>)> {
+        Path::new(working_dir).ancestors().find_map(|dir| {
+            let types: Vec<ProjectType> = ProjectType::ALL
+                .into_iter()
+                .filter(|project_type| project_type.detect(dir))
+                .collect();
+            (!types.is_empty()).then_some((dir, types))
+        })
+    }
+}
+
+impl Segment for ProjectSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let (dir, types) = Self::find_project(&input.workspace.current_dir)?;
+        let primary_type = types[0];
+
+        let mut metadata = HashMap::new();
+        metadata.insert("type".to_string(), primary_type.name().to_string());
+        metadata.insert(
+            "types".to_string(),
+            types.iter().map(|t| t.name()).collect::<Vec<_>>().join(","),
+        );
+        metadata.insert(
+            "project_root".to_string(),
+            dir.to_string_lossy().to_string(),
+        );
+
+        let mut primary = primary_type.name().to_string();
+        if let Some((version, source)) = primary_type.version(dir) {
+            metadata.insert("version".to_string(), version.clone());
+            metadata.insert("version_source".to_string(), source.to_string());
+            if self.show_version {
+                primary = format!("{} {}", primary, version);
+            }
+        }
+
+        let mut overrides = SegmentOverrides::default();
+        if self.type_icons {
+            let (plain, nerd_font) = primary_type.icons();
+            overrides.icon = Some(IconConfig {
+                plain: plain.to_string(),
+                nerd_font: nerd_font.to_string(),
+            });
+        }
+
+        // Other toolchains in the same directory, e.g. a Rust crate with JS bindings
+        let secondary = types[1..]
+            .iter()
+            .map(|t| t.name())
+            .collect::<Vec<_>>()
+            .join(" · ");
+
+        Some(SegmentData {
+            primary,
+            primary_spans: Vec::new(),
+            secondary,
+            secondary_spans: Vec::new(),
+            overrides,
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Project
+    }
+}
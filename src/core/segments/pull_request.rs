@@ -0,0 +1,246 @@
+use super::git::{run_git, GitSegment, GitTimeout};
+use super::{Segment, SegmentData};
+use crate::config::{InputData, SegmentId};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Code hosting service inferred from the remote URL or merge ref
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Forge {
+    GitHub,
+    GitLab,
+    Bitbucket,
+    Gitea,
+    Unknown,
+}
+
+impl Forge {
+    fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "github" => Forge::GitHub,
+            "gitlab" => Forge::GitLab,
+            "bitbucket" => Forge::Bitbucket,
+            "gitea" | "forgejo" | "codeberg" => Forge::Gitea,
+            _ => Forge::Unknown,
+        }
+    }
+
+    fn from_remote_url(url: &str) -> Self {
+        let url = url.to_lowercase();
+        if url.contains("github") {
+            Forge::GitHub
+        } else if url.contains("gitlab") {
+            Forge::GitLab
+        } else if url.contains("bitbucket") {
+            Forge::Bitbucket
+        } else if url.contains("gitea") || url.contains("codeberg") || url.contains("forgejo") {
+            Forge::Gitea
+        } else {
+            Forge::Unknown
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Forge::GitHub => "GitHub",
+            Forge::GitLab => "GitLab",
+            Forge::Bitbucket => "Bitbucket",
+            Forge::Gitea => "Gitea",
+            Forge::Unknown => "Unknown",
+        }
+    }
+
+    /// GitLab calls them merge requests and prefixes numbers with `!`
+    fn format_number(&self, number: u64) -> String {
+        match self {
+            Forge::GitLab => format!("MR !{}", number),
+            _ => format!("PR #{}", number),
+        }
+    }
+}
+
+/// Entry in the optional CI-written cache file, keyed by branch name.
+/// Either a bare number or an object with extra details.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum CachedPullRequest {
+    Number(u64),
+    Details {
+        number: u64,
+        forge: Option<String>,
+        url: Option<String>,
+    },
+}
+
+pub struct PullRequestSegment {
+    cache_file: Option<PathBuf>,
+    show_forge: bool,
+    timeout: Option<Duration>,
+}
+
+impl Default for PullRequestSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PullRequestSegment {
+    pub fn new() -> Self {
+        Self {
+            cache_file: None,
+            show_forge: false,
+            timeout: None,
+        }
+    }
+
+    /// JSON file mapping branch names to PR numbers, written by external tooling
+    pub fn with_cache_file(mut self, cache_file: Option<PathBuf>) -> Self {
+        self.cache_file = cache_file;
+        self
+    }
+
+    pub fn with_forge(mut self, show_forge: bool) -> Self {
+        self.show_forge = show_forge;
+        self
+    }
+
+    /// Limit the total time spent running git for one refresh (None = unbounded)
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Relative cache paths are resolved against the workspace directory
+    fn lookup_cache(&self, working_dir: &str, branch: &str) -> Option<CachedPullRequest> {
+        let path = Path::new(working_dir).join(self.cache_file.as_ref()?);
+        let content = std::fs::read_to_string(path).ok()?;
+        let mut entries: HashMap<String, CachedPullRequest> =
+            serde_json::from_str(&content).ok()?;
+        entries.remove(branch)
+    }
+
+    /// Parse `refs/pull/123/head` style upstream refs set up by `gh pr checkout` and friends
+    fn number_from_merge_ref(merge_ref: &str) -> Option<(u64, Option<Forge>)> {
+        static MERGE_REF: OnceLock<Regex> = OnceLock::new();
+        let re = MERGE_REF.get_or_init(|| {
+            Regex::new(r"^refs/(pull|merge-requests|pull-requests)/(\d+)/(head|from)$")
+                .expect("valid merge ref regex")
+        });
+        let caps = re.captures(merge_ref.trim())?;
+        let number = caps[2].parse().ok()?;
+        let forge = match &caps[1] {
+            "merge-requests" => Some(Forge::GitLab),
+            "pull-requests" => Some(Forge::Bitbucket),
+            _ => None,
+        };
+        Some((number, forge))
+    }
+
+    /// Parse branch naming conventions such as `pr-123`, `pr/123` or `mr/123`
+    fn number_from_branch_name(branch: &str) -> Option<(u64, Option<Forge>)> {
+        static BRANCH_NAME: OnceLock<Regex> = OnceLock::new();
+        let re = BRANCH_NAME.get_or_init(|| {
+            Regex::new(r"(?i)(?:^|/)(pr|pull|mr)[-/_]?(\d+)$").expect("valid branch name regex")
+        });
+        let caps = re.captures(branch)?;
+        let number = caps[2].parse().ok()?;
+        let forge = caps[1].eq_ignore_ascii_case("mr").then_some(Forge::GitLab);
+        Some((number, forge))
+    }
+
+    fn git_config(
+        working_dir: &str,
+        key: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Option<String>, GitTimeout> {
+        let value = run_git(working_dir, &["config", "--get", key], deadline)?;
+        Ok(value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty()))
+    }
+
+    fn remote_forge(
+        working_dir: &str,
+        branch: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Forge, GitTimeout> {
+        let remote = Self::git_config(working_dir, &format!("branch.{}.remote", branch), deadline)?
+            .unwrap_or_else(|| "origin".to_string());
+        let url = Self::git_config(working_dir, &format!("remote.{}.url", remote), deadline)?;
+        Ok(url
+            .map(|url| Forge::from_remote_url(&url))
+            .unwrap_or(Forge::Unknown))
+    }
+}
+
+impl Segment for PullRequestSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let working_dir = &input.workspace.current_dir;
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+
+        let branch = GitSegment::new().get_branch(working_dir, deadline).ok()??;
+
+        let mut url = None;
+        let (number, forge_hint, source) =
+            if let Some(cached) = self.lookup_cache(working_dir, &branch) {
+                match cached {
+                    CachedPullRequest::Number(number) => (number, None, "cache"),
+                    CachedPullRequest::Details {
+                        number,
+                        forge,
+                        url: cached_url,
+                    } => {
+                        url = cached_url;
+                        (number, forge.as_deref().map(Forge::from_name), "cache")
+                    }
+                }
+            } else if let Some((number, forge)) =
+                Self::git_config(working_dir, &format!("branch.{}.merge", branch), deadline)
+                    .ok()
+                    .flatten()
+                    .and_then(|merge_ref| Self::number_from_merge_ref(&merge_ref))
+            {
+                (number, forge, "merge_ref")
+            } else {
+                let (number, forge) = Self::number_from_branch_name(&branch)?;
+                (number, forge, "branch_name")
+            };
+
+        let forge = match forge_hint {
+            Some(forge) if forge != Forge::Unknown => forge,
+            _ => Self::remote_forge(working_dir, &branch, deadline).unwrap_or(Forge::Unknown),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("branch".to_string(), branch);
+        metadata.insert("number".to_string(), number.to_string());
+        metadata.insert("forge".to_string(), forge.name().to_string());
+        metadata.insert("source".to_string(), source.to_string());
+        if let Some(url) = url {
+            metadata.insert("url".to_string(), url);
+        }
+
+        let secondary = if self.show_forge && forge != Forge::Unknown {
+            forge.name().to_string()
+        } else {
+            String::new()
+        };
+
+        Some(SegmentData {
+            primary: forge.format_number(number),
+            primary_spans: Vec::new(),
+            secondary,
+            secondary_spans: Vec::new(),
+            overrides: Default::default(),
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::PullRequest
+    }
+}
@@ -1,13 +1,49 @@
-use super::{Segment, SegmentData};
-use crate::config::{InputData, SegmentId};
+use super::{Segment, SegmentData, TextSpan};
+use crate::config::{InputData, SegmentId, SpanRole};
+use crate::utils::transcript;
 use std::collections::HashMap;
 
+/// What the primary text shows besides the wall-clock session duration
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SessionMode {
+    /// Wall time only
+    #[default]
+    Wall,
+    /// Wall time and time spent in API calls (`12m · api 4m`)
+    Api,
+    /// Wall time and the share of it spent waiting on the model (`12m · api 33%`)
+    Ratio,
+    /// Wall time and average API time per model request (`12m · 8s/turn`)
+    PerTurn,
+    /// All of the above
+    Full,
+}
+
+impl SessionMode {
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "api" => SessionMode::Api,
+            "ratio" => SessionMode::Ratio,
+            "per_turn" => SessionMode::PerTurn,
+            "full" => SessionMode::Full,
+            _ => SessionMode::Wall,
+        }
+    }
+}
+
 #[derive(Default)]
-pub struct SessionSegment;
+pub struct SessionSegment {
+    mode: SessionMode,
+}
 
 impl SessionSegment {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_mode(mut self, mode: SessionMode) -> Self {
+        self.mode = mode;
+        self
     }
 
     fn format_duration(ms: u64) -> String {
@@ -41,25 +77,60 @@ impl Segment for SessionSegment {
         let cost_data = input.cost.as_ref()?;
 
         // Primary display: total duration
-        let primary = if let Some(duration) = cost_data.total_duration_ms {
-            Self::format_duration(duration)
-        } else {
-            return None;
-        };
+        let duration = cost_data.total_duration_ms?;
+        let mut primary_parts = vec![Self::format_duration(duration)];
 
-        // Secondary display: line changes if available
-        let secondary = match (cost_data.total_lines_added, cost_data.total_lines_removed) {
-            (Some(added), Some(removed)) if added > 0 || removed > 0 => {
-                format!("+{} -{}", added, removed)
+        let api_duration = cost_data.total_api_duration_ms;
+        let api_ratio = api_duration
+            .filter(|_| duration > 0)
+            .map(|api| api as f64 / duration as f64 * 100.0);
+        // Counting turns reads the transcript, so only do it when it is displayed
+        let api_turns = matches!(self.mode, SessionMode::PerTurn | SessionMode::Full)
+            .then(|| transcript::api_turns(&input.transcript_path))
+            .filter(|&turns| turns > 0);
+        let api_per_turn = api_duration
+            .zip(api_turns)
+            .map(|(api, turns)| api / turns as u64);
+
+        let api_text = match self.mode {
+            SessionMode::Api => {
+                api_duration.map(|api| format!("api {}", Self::format_duration(api)))
             }
-            (Some(added), None) if added > 0 => {
-                format!("+{}", added)
+            SessionMode::Ratio => api_ratio.map(|ratio| format!("api {:.0}%", ratio)),
+            SessionMode::Full => api_duration.map(|api| match api_ratio {
+                Some(ratio) => format!("api {} ({:.0}%)", Self::format_duration(api), ratio),
+                None => format!("api {}", Self::format_duration(api)),
+            }),
+            SessionMode::Wall | SessionMode::PerTurn => None,
+        };
+        primary_parts.extend(api_text);
+        if let Some(per_turn) = api_per_turn {
+            primary_parts.push(format!("{}/turn", Self::format_duration(per_turn)));
+        }
+
+        let primary = primary_parts.join(" · ");
+
+        // Secondary display: line changes if available, added as positive and removed as negative
+        let added = cost_data.total_lines_added.filter(|&added| added > 0);
+        let removed = cost_data.total_lines_removed.filter(|&removed| removed > 0);
+        let mut secondary_spans = Vec::new();
+        if added.is_some() || removed.is_some() {
+            if cost_data.total_lines_added.is_some() {
+                secondary_spans.push(TextSpan::with_role(
+                    format!("+{}", added.unwrap_or(0)),
+                    SpanRole::Positive,
+                ));
             }
-            (None, Some(removed)) if removed > 0 => {
-                format!("-{}", removed)
+            if cost_data.total_lines_removed.is_some() {
+                if !secondary_spans.is_empty() {
+                    secondary_spans.push(TextSpan::plain(" "));
+                }
+                secondary_spans.push(TextSpan::with_role(
+                    format!("-{}", removed.unwrap_or(0)),
+                    SpanRole::Negative,
+                ));
             }
-            _ => String::new(),
-        };
+        }
 
         let mut metadata = HashMap::new();
         if let Some(duration) = cost_data.total_duration_ms {
@@ -68,6 +139,15 @@ impl Segment for SessionSegment {
         if let Some(api_duration) = cost_data.total_api_duration_ms {
             metadata.insert("api_duration_ms".to_string(), api_duration.to_string());
         }
+        if let Some(ratio) = api_ratio {
+            metadata.insert("api_ratio".to_string(), format!("{:.3}", ratio / 100.0));
+        }
+        if let Some(turns) = api_turns {
+            metadata.insert("api_turns".to_string(), turns.to_string());
+        }
+        if let Some(per_turn) = api_per_turn {
+            metadata.insert("api_per_turn_ms".to_string(), per_turn.to_string());
+        }
         if let Some(added) = cost_data.total_lines_added {
             metadata.insert("lines_added".to_string(), added.to_string());
         }
@@ -75,11 +155,17 @@ impl Segment for SessionSegment {
             metadata.insert("lines_removed".to_string(), removed.to_string());
         }
 
-        Some(SegmentData {
-            primary,
-            secondary,
-            metadata,
-        })
+        Some(
+            SegmentData {
+                primary,
+                primary_spans: Vec::new(),
+                secondary: String::new(),
+                secondary_spans: Vec::new(),
+                overrides: Default::default(),
+                metadata,
+            }
+            .with_secondary_spans(secondary_spans),
+        )
     }
 
     fn id(&self) -> SegmentId {
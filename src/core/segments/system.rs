@@ -0,0 +1,244 @@
+use super::{Segment, SegmentData, SegmentOverrides};
+use crate::config::{InputData, SegmentId};
+use std::collections::HashMap;
+use std::fs;
+
+/// Severity of a resource reading, compared against the configured thresholds
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub enum ResourceLevel {
+    #[default]
+    Normal,
+    Warning,
+    Critical,
+}
+
+impl ResourceLevel {
+    fn from_value(value: f64, thresholds: Thresholds) -> Self {
+        if value >= thresholds.critical {
+            ResourceLevel::Critical
+        } else if value >= thresholds.warning {
+            ResourceLevel::Warning
+        } else {
+            ResourceLevel::Normal
+        }
+    }
+
+    /// The more severe of two levels, so one bad reading colors the whole segment
+    fn worst(self, other: Self) -> Self {
+        if other > self {
+            other
+        } else {
+            self
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResourceLevel::Normal => "normal",
+            ResourceLevel::Warning => "warning",
+            ResourceLevel::Critical => "critical",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Thresholds {
+    pub warning: f64,
+    pub critical: f64,
+}
+
+impl Thresholds {
+    pub fn new(warning: f64, critical: f64) -> Self {
+        Self { warning, critical }
+    }
+}
+
+pub struct SystemSegment {
+    show_load: bool,
+    show_memory: bool,
+    show_disk: bool,
+    /// 1-minute load divided by the number of CPUs
+    load_thresholds: Thresholds,
+    /// Percentage of memory in use
+    memory_thresholds: Thresholds,
+    /// Percentage of the workspace filesystem in use
+    disk_thresholds: Thresholds,
+}
+
+impl Default for SystemSegment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemSegment {
+    pub fn new() -> Self {
+        Self {
+            show_load: true,
+            show_memory: true,
+            show_disk: true,
+            load_thresholds: Thresholds::new(1.0, 2.0),
+            memory_thresholds: Thresholds::new(80.0, 95.0),
+            disk_thresholds: Thresholds::new(90.0, 98.0),
+        }
+    }
+
+    pub fn with_load(mut self, show_load: bool, thresholds: Thresholds) -> Self {
+        self.show_load = show_load;
+        self.load_thresholds = thresholds;
+        self
+    }
+
+    pub fn with_memory(mut self, show_memory: bool, thresholds: Thresholds) -> Self {
+        self.show_memory = show_memory;
+        self.memory_thresholds = thresholds;
+        self
+    }
+
+    pub fn with_disk(mut self, show_disk: bool, thresholds: Thresholds) -> Self {
+        self.show_disk = show_disk;
+        self.disk_thresholds = thresholds;
+        self
+    }
+
+    /// 1, 5 and 15 minute load averages
+    fn read_loadavg() -> Option<(f64, f64, f64)> {
+        let content = fs::read_to_string("/proc/loadavg").ok()?;
+        let mut fields = content.split_whitespace().map(|f| f.parse::<f64>().ok());
+        Some((fields.next()??, fields.next()??, fields.next()??))
+    }
+
+    /// Total and available memory in bytes
+    fn read_meminfo() -> Option<(u64, u64)> {
+        let content = fs::read_to_string("/proc/meminfo").ok()?;
+        let field = |name: &str| -> Option<u64> {
+            let line = content.lines().find(|line| line.starts_with(name))?;
+            let kb: u64 = line[name.len()..]
+                .trim_start_matches(':')
+                .split_whitespace()
+                .next()?
+                .parse()
+                .ok()?;
+            Some(kb * 1024)
+        };
+        Some((field("MemTotal")?, field("MemAvailable")?))
+    }
+
+    /// Total and available bytes of the filesystem holding `path`
+    #[cfg(unix)]
+    fn read_disk(path: &str) -> Option<(u64, u64)> {
+        use std::ffi::CString;
+
+        let c_path = CString::new(path).ok()?;
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        // SAFETY: c_path is a valid NUL-terminated string and stat is a valid out pointer
+        if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+            return None;
+        }
+
+        let fragment = stat.f_frsize as u64;
+        Some((
+            stat.f_blocks as u64 * fragment,
+            stat.f_bavail as u64 * fragment,
+        ))
+    }
+
+    #[cfg(not(unix))]
+    fn read_disk(_path: &str) -> Option<(u64, u64)> {
+        None
+    }
+
+    fn format_bytes(bytes: u64) -> String {
+        const UNITS: [&str; 5] = ["B", "K", "M", "G", "T"];
+        let mut value = bytes as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+
+        if value < 10.0 && unit > 0 {
+            format!("{:.1}{}", value, UNITS[unit])
+        } else {
+            format!("{:.0}{}", value, UNITS[unit])
+        }
+    }
+
+    fn used_percent(total: u64, available: u64) -> f64 {
+        if total == 0 {
+            0.0
+        } else {
+            total.saturating_sub(available) as f64 / total as f64 * 100.0
+        }
+    }
+}
+
+impl Segment for SystemSegment {
+    fn collect(&self, input: &InputData) -> Option<SegmentData> {
+        let mut parts = Vec::new();
+        let mut metadata = HashMap::new();
+        let mut level = ResourceLevel::Normal;
+
+        if self.show_load {
+            if let Some((load1, load5, load15)) = Self::read_loadavg() {
+                let cpus = std::thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(1);
+                let per_cpu = load1 / cpus as f64;
+                level = level.worst(ResourceLevel::from_value(per_cpu, self.load_thresholds));
+
+                metadata.insert("load1".to_string(), format!("{:.2}", load1));
+                metadata.insert("load5".to_string(), format!("{:.2}", load5));
+                metadata.insert("load15".to_string(), format!("{:.2}", load15));
+                metadata.insert("cpus".to_string(), cpus.to_string());
+                parts.push(format!("load {:.2}", load1));
+            }
+        }
+
+        if self.show_memory {
+            if let Some((total, available)) = Self::read_meminfo() {
+                let used = Self::used_percent(total, available);
+                level = level.worst(ResourceLevel::from_value(used, self.memory_thresholds));
+
+                metadata.insert("mem_total_bytes".to_string(), total.to_string());
+                metadata.insert("mem_available_bytes".to_string(), available.to_string());
+                metadata.insert("mem_used_percent".to_string(), format!("{:.1}", used));
+                parts.push(format!("mem {}", Self::format_bytes(available)));
+            }
+        }
+
+        if self.show_disk {
+            if let Some((total, available)) = Self::read_disk(&input.workspace.current_dir) {
+                let used = Self::used_percent(total, available);
+                level = level.worst(ResourceLevel::from_value(used, self.disk_thresholds));
+
+                metadata.insert("disk_total_bytes".to_string(), total.to_string());
+                metadata.insert("disk_available_bytes".to_string(), available.to_string());
+                metadata.insert("disk_used_percent".to_string(), format!("{:.1}", used));
+                parts.push(format!("disk {}", Self::format_bytes(available)));
+            }
+        }
+
+        if parts.is_empty() {
+            return None;
+        }
+
+        metadata.insert("level".to_string(), level.name().to_string());
+
+        Some(SegmentData {
+            primary: parts.join(" · "),
+            primary_spans: Vec::new(),
+            secondary: String::new(),
+            secondary_spans: Vec::new(),
+            overrides: SegmentOverrides {
+                level,
+                ..Default::default()
+            },
+            metadata,
+        })
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::System
+    }
+}
@@ -18,7 +18,10 @@ impl Segment for UpdateSegment {
 
         update_state.status_text().map(|status_text| SegmentData {
             primary: status_text,
+            primary_spans: Vec::new(),
             secondary: String::new(),
+            secondary_spans: Vec::new(),
+            overrides: Default::default(),
             metadata: std::collections::HashMap::new(),
         })
     }
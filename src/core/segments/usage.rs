@@ -1,33 +1,72 @@
-use super::{Segment, SegmentData};
-use crate::config::{InputData, ModelConfig, SegmentId, TranscriptEntry};
+use super::{Segment, SegmentData, TextSpan};
+use crate::config::{
+    InputData, ModelConfig, ModelEntry, NormalizedUsage, SegmentId, SpanRole, TranscriptEntry,
+};
 use std::collections::HashMap;
 use std::fs;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
-#[derive(Default)]
-pub struct UsageSegment;
+/// Context usage from which the percentage is shown in the warning color
+const WARNING_PERCENTAGE: f64 = 80.0;
 
-impl UsageSegment {
+pub struct UsageSegment<'a> {
+    model_config: Option<&'a ModelConfig>,
+    show_provider: bool,
+}
+
+impl Default for UsageSegment<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> UsageSegment<'a> {
     pub fn new() -> Self {
-        Self
+        Self {
+            model_config: None,
+            show_provider: true,
+        }
+    }
+
+    /// Show the `provider` declared in models.toml as a badge (on by default)
+    pub fn with_provider(mut self, show_provider: bool) -> Self {
+        self.show_provider = show_provider;
+        self
     }
 
-    /// Get context limit for the specified model
-    fn get_context_limit_for_model(model_id: &str) -> u32 {
-        let model_config = ModelConfig::load();
-        model_config.get_context_limit(model_id)
+    /// Share an already loaded model config instead of loading it again
+    pub fn with_model_config(mut self, model_config: &'a ModelConfig) -> Self {
+        self.model_config = Some(model_config);
+        self
+    }
+
+    /// Matching models.toml entry for the specified model
+    fn get_entry_for_model(&self, model_id: &str) -> Option<ModelEntry> {
+        match self.model_config {
+            Some(model_config) => model_config.get_entry(model_id).cloned(),
+            None => ModelConfig::load().get_entry(model_id).cloned(),
+        }
     }
 }
 
-impl Segment for UsageSegment {
+impl Segment for UsageSegment<'_> {
     fn collect(&self, input: &InputData) -> Option<SegmentData> {
-        // Dynamically determine context limit based on current model ID
-        let context_limit = Self::get_context_limit_for_model(&input.model.id);
-
-        let context_used_token_opt = parse_transcript_usage(&input.transcript_path);
+        // Dynamically determine context limit based on current model ID,
+        // leaving out the tokens the backend reserves for its response
+        let entry = self.get_entry_for_model(&input.model.id);
+        let context_limit = entry.as_ref().map_or(200_000, |e| e.input_limit());
+        let reports_cache = entry.as_ref().and_then(|e| e.reports_cache).unwrap_or(true);
+
+        let context_used_token_opt = parse_transcript_usage(&input.transcript_path).map(|usage| {
+            if reports_cache {
+                usage.display_tokens()
+            } else {
+                usage.context_tokens_excluding_cache()
+            }
+        });
 
-        let (percentage_display, tokens_display) = match context_used_token_opt {
+        let (percentage_display, tokens_display, percentage_role) = match context_used_token_opt {
             Some(context_used_token) => {
                 let context_used_rate = (context_used_token as f64 / context_limit as f64) * 100.0;
 
@@ -48,11 +87,17 @@ impl Segment for UsageSegment {
                     context_used_token.to_string()
                 };
 
-                (percentage, tokens)
+                let role = if context_used_rate >= WARNING_PERCENTAGE {
+                    SpanRole::Warning
+                } else {
+                    SpanRole::Value
+                };
+
+                (percentage, tokens, role)
             }
             None => {
                 // No usage data available
-                ("-".to_string(), "-".to_string())
+                ("-".to_string(), "-".to_string(), SpanRole::Value)
             }
         };
 
@@ -69,13 +114,38 @@ impl Segment for UsageSegment {
             }
         }
         metadata.insert("limit".to_string(), context_limit.to_string());
+        if let Some(max_output) = entry.as_ref().and_then(|e| e.max_output_tokens) {
+            metadata.insert("max_output_tokens".to_string(), max_output.to_string());
+        }
+        let provider = entry.as_ref().and_then(|e| e.provider.clone());
+        if let Some(provider) = &provider {
+            metadata.insert("provider".to_string(), provider.clone());
+        }
         metadata.insert("model".to_string(), input.model.id.clone());
 
-        Some(SegmentData {
-            primary: format!("{} · {} tokens", percentage_display, tokens_display),
-            secondary: String::new(),
-            metadata,
-        })
+        Some(
+            SegmentData {
+                primary: String::new(),
+                primary_spans: Vec::new(),
+                secondary: String::new(),
+                secondary_spans: Vec::new(),
+                overrides: Default::default(),
+                metadata,
+            }
+            .with_primary_spans(vec![
+                TextSpan::with_role(percentage_display, percentage_role),
+                TextSpan::with_role(" · ", SpanRole::Muted),
+                TextSpan::with_role(tokens_display, SpanRole::Value),
+                TextSpan::with_role(" tokens", SpanRole::Unit),
+            ])
+            .with_secondary_spans(
+                provider
+                    .filter(|_| self.show_provider)
+                    .map(|provider| TextSpan::with_role(provider, SpanRole::Muted))
+                    .into_iter()
+                    .collect(),
+            ),
+        )
     }
 
     fn id(&self) -> SegmentId {
@@ -83,7 +153,7 @@ impl Segment for UsageSegment {
     }
 }
 
-fn parse_transcript_usage<P: AsRef<Path>>(transcript_path: P) -> Option<u32> {
+fn parse_transcript_usage<P: AsRef<Path>>(transcript_path: P) -> Option<NormalizedUsage> {
     let path = transcript_path.as_ref();
 
     // Try to parse from current transcript file
@@ -101,7 +171,7 @@ fn parse_transcript_usage<P: AsRef<Path>>(transcript_path: P) -> Option<u32> {
     None
 }
 
-fn try_parse_transcript_file(path: &Path) -> Option<u32> {
+fn try_parse_transcript_file(path: &Path) -> Option<NormalizedUsage> {
     let file = fs::File::open(path).ok()?;
     let reader = BufReader::new(file);
     let lines: Vec<String> = reader
@@ -136,8 +206,7 @@ fn try_parse_transcript_file(path: &Path) -> Option<u32> {
             if entry.r#type.as_deref() == Some("assistant") {
                 if let Some(message) = &entry.message {
                     if let Some(raw_usage) = &message.usage {
-                        let normalized = raw_usage.clone().normalize();
-                        return Some(normalized.display_tokens());
+                        return Some(raw_usage.clone().normalize());
                     }
                 }
             }
@@ -147,7 +216,7 @@ fn try_parse_transcript_file(path: &Path) -> Option<u32> {
     None
 }
 
-fn find_usage_by_leaf_uuid(leaf_uuid: &str, project_dir: &Path) -> Option<u32> {
+fn find_usage_by_leaf_uuid(leaf_uuid: &str, project_dir: &Path) -> Option<NormalizedUsage> {
     // Search for the leafUuid across all session files in the project directory
     let entries = fs::read_dir(project_dir).ok()?;
 
@@ -167,7 +236,7 @@ fn find_usage_by_leaf_uuid(leaf_uuid: &str, project_dir: &Path) -> Option<u32> {
     None
 }
 
-fn search_uuid_in_file(path: &Path, target_uuid: &str) -> Option<u32> {
+fn search_uuid_in_file(path: &Path, target_uuid: &str) -> Option<NormalizedUsage> {
     let file = fs::File::open(path).ok()?;
     let reader = BufReader::new(file);
     let lines: Vec<String> = reader
@@ -190,8 +259,7 @@ fn search_uuid_in_file(path: &Path, target_uuid: &str) -> Option<u32> {
                         // Direct assistant message with usage
                         if let Some(message) = &entry.message {
                             if let Some(raw_usage) = &message.usage {
-                                let normalized = raw_usage.clone().normalize();
-                                return Some(normalized.display_tokens());
+                                return Some(raw_usage.clone().normalize());
                             }
                         }
                     } else if entry.r#type.as_deref() == Some("user") {
@@ -209,7 +277,7 @@ fn search_uuid_in_file(path: &Path, target_uuid: &str) -> Option<u32> {
     None
 }
 
-fn find_assistant_message_by_uuid(lines: &[String], target_uuid: &str) -> Option<u32> {
+fn find_assistant_message_by_uuid(lines: &[String], target_uuid: &str) -> Option<NormalizedUsage> {
     for line in lines {
         let line = line.trim();
         if line.is_empty() {
@@ -221,8 +289,7 @@ fn find_assistant_message_by_uuid(lines: &[String], target_uuid: &str) -> Option
                 if uuid == target_uuid && entry.r#type.as_deref() == Some("assistant") {
                     if let Some(message) = &entry.message {
                         if let Some(raw_usage) = &message.usage {
-                            let normalized = raw_usage.clone().normalize();
-                            return Some(normalized.display_tokens());
+                            return Some(raw_usage.clone().normalize());
                         }
                     }
                 }
@@ -233,7 +300,7 @@ fn find_assistant_message_by_uuid(lines: &[String], target_uuid: &str) -> Option
     None
 }
 
-fn try_find_usage_from_project_history(transcript_path: &Path) -> Option<u32> {
+fn try_find_usage_from_project_history(transcript_path: &Path) -> Option<NormalizedUsage> {
     let project_dir = transcript_path.parent()?;
 
     // Find the most recent session file in the project directory
@@ -1,68 +1,330 @@
-use crate::config::{AnsiColor, Config, SegmentConfig, StyleMode};
-use crate::core::segments::SegmentData;
-
-/// Strip ANSI escape sequences and return visible text length
-fn visible_width(text: &str) -> usize {
-    let mut visible = String::new();
-    let mut in_escape = false;
-    let mut chars = text.chars().peekable();
-
-    while let Some(ch) = chars.next() {
-        if ch == '\x1b' {
-            // Start of ANSI escape sequence
-            in_escape = true;
-            // Skip the [ character
-            if chars.peek() == Some(&'[') {
-                chars.next();
-            }
-        } else if in_escape {
-            // Skip until we find the end of the escape sequence (letter)
-            if ch.is_alphabetic() {
-                in_escape = false;
-            }
-        } else {
-            // Regular character
-            visible.push(ch);
+use crate::config::{
+    Alignment, AnsiColor, ColorDepth, Config, SegmentConfig, SeparatorColor, SpanRole, StyleMode,
+    StyleTarget, TextAttribute, TextStyleConfig, UnderlineStyle,
+};
+use crate::core::output::OutputFormat;
+use crate::core::segments::{SegmentData, TextSpan};
+use crate::utils::color::{detect_color_depth, downsample, strip_ansi, to_hex};
+use crate::utils::width::{truncate_end, visible_width};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Shortest a segment's text is cut to before the segment is dropped instead
+const MIN_TRUNCATED_WIDTH: usize = 6;
+
+/// How a separator glyph blends the backgrounds on either side of it
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SeparatorKind {
+    /// Solid Powerline glyph pointing right, e.g. `\u{e0b0}`, `\u{e0b4}`, `\u{e0bc}`
+    Right,
+    /// Solid Powerline glyph pointing left, e.g. `\u{e0b2}`, `\u{e0b6}`, `\u{e0be}`
+    Left,
+    /// Outline Powerline glyph, e.g. `\u{e0b1}`, used as a same-color divider
+    Thin,
+    /// Any other separator text
+    Plain,
+}
+
+impl SeparatorKind {
+    fn of(separator: &str) -> Self {
+        let mut chars = separator.chars();
+        match (chars.next(), chars.next()) {
+            // U+E0B0..U+E0BF come in pairs of solid and outline glyphs,
+            // alternating between right- and left-facing shapes
+            (Some(glyph @ '\u{e0b0}'..='\u{e0bf}'), None) => match (glyph as u32 - 0xe0b0) % 4 {
+                0 => SeparatorKind::Right,
+                2 => SeparatorKind::Left,
+                _ => SeparatorKind::Thin,
+            },
+            _ => SeparatorKind::Plain,
         }
     }
-
-    visible.chars().count()
 }
 
 pub struct StatusLineGenerator {
     config: Config,
+    color_depth: ColorDepth,
+    format: OutputFormat,
 }
 
 impl StatusLineGenerator {
     pub fn new(config: Config) -> Self {
-        Self { config }
+        let color_depth = config
+            .terminal
+            .color_depth
+            .unwrap_or_else(detect_color_depth);
+        Self {
+            config,
+            color_depth,
+            format: OutputFormat::Ansi,
+        }
+    }
+
+    /// Print in another format. Formats not shown in a terminal use full color
+    /// unless the config pins a color depth.
+    pub fn with_format(mut self, format: OutputFormat) -> Self {
+        if !format.is_terminal() {
+            self.color_depth = self
+                .config
+                .terminal
+                .color_depth
+                .unwrap_or(ColorDepth::TrueColor);
+        }
+        self.format = format;
+        self
     }
 
     pub fn generate(&self, segments: Vec<(SegmentConfig, SegmentData)>) -> String {
-        let mut output = Vec::new();
-        let enabled_segments: Vec<_> = segments
-            .into_iter()
-            .filter(|(config, _)| config.enabled)
+        let rows = self.split_rows(segments);
+        let Some(backend) = self.format.backend() else {
+            return self.generate_json(&rows);
+        };
+
+        let lines = self
+            .render_lines(&rows)
+            .iter()
+            .map(|line| backend.line(line))
             .collect();
+        backend.document(lines)
+    }
 
-        for (config, data) in enabled_segments.iter() {
-            let rendered = self.render_segment(config, data);
-            if !rendered.is_empty() {
-                output.push(rendered);
+    /// Lines rendered with ANSI sequences, or as plain text without color support
+    fn render_lines(&self, rows: &[(Vec<(SegmentConfig, SegmentData)>, String)]) -> Vec<String> {
+        rows.iter()
+            .map(|(row, separator)| self.generate_row(row, separator))
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                if self.color_depth == ColorDepth::None {
+                    strip_ansi(&line)
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+
+    /// Segments shown on the lines, with their text, metadata and resolved
+    /// colors, plus the plain lines
+    fn generate_json(&self, rows: &[(Vec<(SegmentConfig, SegmentData)>, String)]) -> String {
+        let hex = |color: Option<&AnsiColor>| color.map(|color| to_hex(&self.fit_color(color)));
+        let spans = |spans: &[TextSpan]| -> Vec<serde_json::Value> {
+            spans
+                .iter()
+                .map(|span| {
+                    let role_color = span
+                        .role
+                        .and_then(|role| self.config.style.roles.color(role));
+                    serde_json::json!({
+                        "text": span.text,
+                        "role": span.role,
+                        "color": hex(span.color.as_ref().or(role_color)),
+                    })
+                })
+                .collect()
+        };
+
+        let segments_json: Vec<_> = rows
+            .iter()
+            .flat_map(|(row, _)| row)
+            .map(|(config, data)| {
+                let metadata: BTreeMap<_, _> = data.metadata.iter().collect();
+                serde_json::json!({
+                    "id": config.id,
+                    "icon": self.get_icon(config),
+                    "primary": data.primary,
+                    "secondary": data.secondary,
+                    "primary_spans": spans(&data.primary_spans),
+                    "secondary_spans": spans(&data.secondary_spans),
+                    "colors": {
+                        "icon": hex(config.colors.icon.as_ref()),
+                        "text": hex(config.colors.text.as_ref()),
+                        "secondary": hex(config.colors.secondary.as_ref().or(config.colors.text.as_ref())),
+                        "background": hex(config.colors.background.as_ref()),
+                    },
+                    "metadata": metadata,
+                })
+            })
+            .collect();
+
+        let lines: Vec<String> = self
+            .render_lines(rows)
+            .iter()
+            .map(|line| strip_ansi(line))
+            .collect();
+
+        serde_json::json!({
+            "lines": lines,
+            "segments": segments_json,
+        })
+        .to_string()
+    }
+
+    /// Render one line, padding the right-aligned block to the terminal width
+    /// and giving way by priority when the line does not fit
+    fn generate_row(&self, segments: &[(SegmentConfig, SegmentData)], separator: &str) -> String {
+        let Some(width) = self.terminal_width() else {
+            // Without a known width the right block simply follows the left one
+            let mut ordered = segments.to_vec();
+            ordered.sort_by_key(|(config, _)| config.layout.align == Alignment::Right);
+            return self.join_block(&ordered, separator);
+        };
+
+        let mut segments = segments.to_vec();
+        self.fit_row(&mut segments, separator, width);
+
+        let (left, right) = self.render_blocks(&segments, separator);
+        if right.is_empty() {
+            return left;
+        }
+        let used = self.visible_width(&left) + self.visible_width(&right);
+        let min_gap = usize::from(!left.is_empty());
+        let padding = width.saturating_sub(used).max(min_gap);
+        format!("{}{}{}", left, " ".repeat(padding), right)
+    }
+
+    fn visible_width(&self, text: &str) -> usize {
+        visible_width(text, &self.config.terminal)
+    }
+
+    /// Configured width, then `COLUMNS`
+    fn terminal_width(&self) -> Option<usize> {
+        self.config
+            .terminal
+            .width
+            .or_else(|| std::env::var("COLUMNS").ok()?.trim().parse().ok())
+            .filter(|&width| width > 0)
+    }
+
+    /// Render the left- and right-aligned blocks of a row
+    fn render_blocks(
+        &self,
+        segments: &[(SegmentConfig, SegmentData)],
+        separator: &str,
+    ) -> (String, String) {
+        let (left, right): (Vec<_>, Vec<_>) = segments
+            .iter()
+            .cloned()
+            .partition(|(config, _)| config.layout.align == Alignment::Left);
+        (
+            self.join_block(&left, separator),
+            self.join_block(&right, separator),
+        )
+    }
+
+    /// Shorten, then drop, the lowest-priority segments until the row fits in
+    /// `width` columns. A segment first loses its secondary text, then has its
+    /// primary text cut, and is removed once that would leave too little.
+    fn fit_row(
+        &self,
+        segments: &mut Vec<(SegmentConfig, SegmentData)>,
+        separator: &str,
+        width: usize,
+    ) {
+        loop {
+            let (left, right) = self.render_blocks(segments, separator);
+            let gap = usize::from(!left.is_empty() && !right.is_empty());
+            let used = self.visible_width(&left) + self.visible_width(&right) + gap;
+            if used <= width {
+                return;
+            }
+
+            // Ties give way from the end of the line
+            let Some(index) = (0..segments.len())
+                .rev()
+                .min_by_key(|&i| segments[i].0.layout.priority)
+            else {
+                return;
+            };
+
+            let overflow = used - width;
+            let data = &mut segments[index].1;
+            if !data.secondary.is_empty() {
+                data.secondary.clear();
+                data.secondary_spans.clear();
+                continue;
+            }
+            let primary_width = self.visible_width(&data.primary);
+            let target = primary_width.saturating_sub(overflow);
+            let truncated = truncate_end(&data.primary, target, &self.config.terminal);
+            // Drop the segment rather than keep a stub, or loop on a cut that saves nothing
+            if target >= MIN_TRUNCATED_WIDTH && self.visible_width(&truncated) < primary_width {
+                // Cut text loses its spans and is drawn in the segment's color
+                data.primary = truncated;
+                data.primary_spans.clear();
+            } else {
+                segments.remove(index);
             }
         }
+    }
 
-        if output.is_empty() {
+    /// Render segments joined by `separator`, framed by the configured caps
+    fn join_block(
+        &self,
+        enabled_segments: &[(SegmentConfig, SegmentData)],
+        separator: &str,
+    ) -> String {
+        let rendered: Vec<_> = enabled_segments
+            .iter()
+            .map(|(config, data)| (self.render_segment(config, data), config))
+            .filter(|(rendered, _)| !rendered.is_empty())
+            .collect();
+
+        let (Some((_, first)), Some((_, last))) = (rendered.first(), rendered.last()) else {
             return String::new();
+        };
+
+        let mut result = self.start_cap(first);
+        for (i, (segment, config)) in rendered.iter().enumerate() {
+            if i > 0 {
+                result.push_str(&self.create_separator(separator, rendered[i - 1].1, config));
+            }
+            result.push_str(segment);
         }
+        result.push_str(&self.end_cap(last));
 
-        // Handle Powerline arrow separators with color transition
-        if self.config.style.separator == "\u{e0b0}" {
-            self.join_with_powerline_arrows(&output, &enabled_segments)
-        } else {
-            // For all other separators, use white color and simple join
-            self.join_with_white_separators(&output)
+        // Reset colors at the end of colored transitions
+        if SeparatorKind::of(separator) != SeparatorKind::Plain && rendered.len() > 1 {
+            result.push_str("\x1b[0m");
         }
+        result
+    }
+
+    /// Group enabled segments by the configured rows, each with its separator.
+    /// Segments not listed in any row are appended to the first line.
+    fn split_rows(
+        &self,
+        segments: Vec<(SegmentConfig, SegmentData)>,
+    ) -> Vec<(Vec<(SegmentConfig, SegmentData)>, String)> {
+        let mut remaining: Vec<_> = segments
+            .into_iter()
+            .filter(|(config, _)| config.enabled)
+            .collect();
+        let default_separator = &self.config.style.separator;
+
+        if self.config.rows.is_empty() {
+            return vec![(remaining, default_separator.clone())];
+        }
+
+        let mut rows: Vec<_> = self
+            .config
+            .rows
+            .iter()
+            .map(|row| {
+                let segments = row
+                    .segments
+                    .iter()
+                    .filter_map(|id| {
+                        let index = remaining.iter().position(|(config, _)| config.id == *id)?;
+                        Some(remaining.remove(index))
+                    })
+                    .collect::<Vec<_>>();
+                let separator = row.separator.as_ref().unwrap_or(default_separator);
+                (segments, separator.clone())
+            })
+            .collect();
+        rows[0].0.extend(remaining);
+        rows
     }
 
     /// Generate statusline for TUI preview with proper width calculation
@@ -97,20 +359,49 @@ impl StatusLineGenerator {
         use ansi_to_tui::IntoText;
         use ratatui::text::{Line, Span, Text};
 
-        let enabled_segments: Vec<_> = segments
-            .into_iter()
-            .filter(|(config, _)| config.enabled)
+        let max_w = max_width as usize;
+        let lines: Vec<String> = self
+            .split_rows(segments)
+            .iter()
+            .flat_map(|(row, separator)| self.wrap_row(row, separator, max_w))
             .collect();
 
-        if enabled_segments.is_empty() {
-            return Text::from(vec![Line::default()]);
+        // Convert string lines to ratatui Text
+        let mut tui_lines = Vec::new();
+        for line in lines {
+            if let Ok(text) = line.into_text() {
+                for tui_line in text.lines {
+                    tui_lines.push(tui_line);
+                }
+            } else {
+                tui_lines.push(Line::from(vec![Span::raw(line)]));
+            }
         }
 
+        // Ensure we have at least one line
+        if tui_lines.is_empty() {
+            tui_lines.push(Line::default());
+        }
+
+        Text::from(tui_lines)
+    }
+
+    /// Wrap one row at segment boundaries so no preview line exceeds `max_w`
+    fn wrap_row(
+        &self,
+        enabled_segments: &[(SegmentConfig, SegmentData)],
+        separator: &str,
+        max_w: usize,
+    ) -> Vec<String> {
+        // Right-aligned segments wrap after the left-aligned ones
+        let mut ordered = enabled_segments.to_vec();
+        ordered.sort_by_key(|(config, _)| config.layout.align == Alignment::Right);
+
         // Render each segment individually
         let mut rendered_segments = Vec::new();
         let mut segment_configs = Vec::new();
 
-        for (config, data) in &enabled_segments {
+        for (config, data) in &ordered {
             let rendered = self.render_segment(config, data);
             if !rendered.is_empty() {
                 rendered_segments.push(rendered);
@@ -119,37 +410,31 @@ impl StatusLineGenerator {
         }
 
         if rendered_segments.is_empty() {
-            return Text::from(vec![Line::default()]);
+            return Vec::new();
         }
 
-        // Pre-calculate separators between segments
-        let mut separators = Vec::new();
-        for i in 0..rendered_segments.len().saturating_sub(1) {
-            let separator = if self.config.style.separator == "\u{e0b0}" {
-                // Powerline arrows with color transition
-                let prev_bg = segment_configs
-                    .get(i)
-                    .and_then(|config| config.colors.background.as_ref());
-                let curr_bg = segment_configs
-                    .get(i + 1)
-                    .and_then(|config| config.colors.background.as_ref());
-                self.create_powerline_arrow(prev_bg, curr_bg)
-            } else {
-                // Regular separators with white color
-                format!("\x1b[37m{}\x1b[0m", self.config.style.separator)
-            };
-            separators.push(separator);
+        // Caps stay attached to the first and last segment when lines wrap
+        if let (Some(first), Some(last)) = (segment_configs.first(), segment_configs.last()) {
+            rendered_segments[0].insert_str(0, &self.start_cap(first));
+            if let Some(last_segment) = rendered_segments.last_mut() {
+                last_segment.push_str(&self.end_cap(last));
+            }
         }
 
+        // Pre-calculate separators between segments
+        let separators: Vec<String> = segment_configs
+            .windows(2)
+            .map(|pair| self.create_separator(separator, &pair[0], &pair[1]))
+            .collect();
+
         // Intelligent line wrapping by segment
         let mut lines: Vec<String> = Vec::new();
         let mut current_line = String::new();
         let mut current_width = 0usize;
-        let max_w = max_width as usize;
 
         for i in 0..rendered_segments.len() {
             let segment = &rendered_segments[i];
-            let segment_width = visible_width(segment);
+            let segment_width = self.visible_width(segment);
 
             // Check if adding this segment would exceed max_width
             if current_width > 0 && current_width + segment_width > max_w {
@@ -166,12 +451,12 @@ impl StatusLineGenerator {
             // Handle separator if not the last segment
             if i < separators.len() {
                 let separator = &separators[i];
-                let separator_width = visible_width(separator);
+                let separator_width = self.visible_width(separator);
 
                 // Check if next segment exists
                 if i + 1 < rendered_segments.len() {
                     let next_segment = &rendered_segments[i + 1];
-                    let next_width = visible_width(next_segment);
+                    let next_width = self.visible_width(next_segment);
 
                     // Check if separator AND next segment both fit
                     if current_width + separator_width + next_width <= max_w {
@@ -194,59 +479,40 @@ impl StatusLineGenerator {
             lines.push(current_line);
         }
 
-        // Convert string lines to ratatui Text
-        let mut tui_lines = Vec::new();
-        for line in lines {
-            if let Ok(text) = line.into_text() {
-                for tui_line in text.lines {
-                    tui_lines.push(tui_line);
-                }
-            } else {
-                tui_lines.push(Line::from(vec![Span::raw(line)]));
-            }
-        }
-
-        // Ensure we have at least one line
-        if tui_lines.is_empty() {
-            tui_lines.push(Line::default());
-        }
-
-        Text::from(tui_lines)
+        lines
     }
 
     fn render_segment(&self, config: &SegmentConfig, data: &SegmentData) -> String {
         let icon = self.get_icon(config);
+        let icon_attributes = self.attribute_codes(&config.styles, StyleTarget::Icon);
+        let text_attributes = self.attribute_codes(&config.styles, StyleTarget::Text);
 
         // Apply background color to the entire segment if set
         if let Some(bg_color) = &config.colors.background {
             let bg_code = self.apply_background_color(bg_color);
+            // Resets inside the segment must restore its background
+            let reset = format!("\x1b[0m{}", bg_code);
 
             // Build the entire segment content first
-            let icon_colored = if let Some(icon_color) = &config.colors.icon {
-                self.apply_color(&icon, Some(icon_color))
-                    .replace("\x1b[0m", "")
-            } else {
-                icon.clone()
-            };
+            let icon_colored = self
+                .apply_style(&icon, config.colors.icon.as_ref(), &icon_attributes)
+                .replace("\x1b[0m", &reset);
 
             let text_styled = self
-                .apply_style(
+                .render_text(
                     &data.primary,
+                    &data.primary_spans,
                     config.colors.text.as_ref(),
-                    config.styles.text_bold,
+                    &text_attributes,
                 )
-                .replace("\x1b[0m", "");
+                .replace("\x1b[0m", &reset);
 
             let mut segment_content = format!(" {} {} ", icon_colored, text_styled);
 
             if !data.secondary.is_empty() {
                 let secondary_styled = self
-                    .apply_style(
-                        &data.secondary,
-                        config.colors.text.as_ref(),
-                        config.styles.text_bold,
-                    )
-                    .replace("\x1b[0m", "");
+                    .render_secondary(config, data)
+                    .replace("\x1b[0m", &reset);
                 segment_content.push_str(&format!("{} ", secondary_styled));
             }
 
@@ -254,30 +520,120 @@ impl StatusLineGenerator {
             format!("{}{}\x1b[49m", bg_code, segment_content)
         } else {
             // No background color, use original logic
-            let icon_colored = self.apply_color(&icon, config.colors.icon.as_ref());
-            let text_styled = self.apply_style(
+            let icon_colored =
+                self.apply_style(&icon, config.colors.icon.as_ref(), &icon_attributes);
+            let text_styled = self.render_text(
                 &data.primary,
+                &data.primary_spans,
                 config.colors.text.as_ref(),
-                config.styles.text_bold,
+                &text_attributes,
             );
 
             let mut segment = format!("{} {}", icon_colored, text_styled);
 
             if !data.secondary.is_empty() {
-                segment.push_str(&format!(
-                    " {}",
-                    self.apply_style(
-                        &data.secondary,
-                        config.colors.text.as_ref(),
-                        config.styles.text_bold
-                    )
-                ));
+                segment.push_str(&format!(" {}", self.render_secondary(config, data)));
             }
 
             segment
         }
     }
 
+    /// Text in the segment's color and attributes, span by span when the segment provides spans
+    fn render_text(
+        &self,
+        text: &str,
+        spans: &[TextSpan],
+        color: Option<&AnsiColor>,
+        attributes: &[String],
+    ) -> String {
+        if spans.is_empty() {
+            return self.apply_style(text, color, attributes);
+        }
+
+        spans
+            .iter()
+            .map(|span| {
+                let role_color = span
+                    .role
+                    .and_then(|role| self.config.style.roles.color(role));
+                let span_color = span.color.as_ref().or(role_color).or(color);
+                // Without a theme color, muted text is dimmed instead
+                if span.role == Some(SpanRole::Muted) && role_color.is_none() {
+                    let mut dimmed = attributes.to_vec();
+                    dimmed.push("2".to_string());
+                    self.apply_style(&span.text, span_color, &dimmed)
+                } else {
+                    self.apply_style(&span.text, span_color, attributes)
+                }
+            })
+            .collect()
+    }
+
+    /// Secondary text in its own color and style, falling back to the text color
+    fn render_secondary(&self, config: &SegmentConfig, data: &SegmentData) -> String {
+        let attributes = self.attribute_codes(&config.styles, StyleTarget::Secondary);
+        let color = config
+            .colors
+            .secondary
+            .as_ref()
+            .or(config.colors.text.as_ref());
+        self.render_text(&data.secondary, &data.secondary_spans, color, &attributes)
+    }
+
+    /// SGR codes for the attributes enabled on a segment's icon, text or secondary text
+    fn attribute_codes(&self, styles: &TextStyleConfig, target: StyleTarget) -> Vec<String> {
+        let mut codes = Vec::new();
+
+        for attribute in TextAttribute::ALL {
+            if !styles.is_set(target, attribute) {
+                continue;
+            }
+            let code = match attribute {
+                TextAttribute::Bold => "1",
+                TextAttribute::Dim => "2",
+                TextAttribute::Italic => "3",
+                TextAttribute::Underline => "4",
+                TextAttribute::Reverse => "7",
+                TextAttribute::Strikethrough => "9",
+            };
+            codes.push(code.to_string());
+
+            if attribute != TextAttribute::Underline {
+                continue;
+            }
+
+            // Underline styles and colors are colon-separated extensions (kitty, VTE,
+            // WezTerm); terminals without support keep the plain underline above
+            let underline_style = match styles.underline_style {
+                UnderlineStyle::Straight => None,
+                UnderlineStyle::Double => Some("4:2"),
+                UnderlineStyle::Curly => Some("4:3"),
+                UnderlineStyle::Dotted => Some("4:4"),
+                UnderlineStyle::Dashed => Some("4:5"),
+            };
+            codes.extend(underline_style.map(str::to_string));
+
+            // Colored underlines use the extended palette, so skip them on 16-color terminals
+            if self.color_depth >= ColorDepth::Ansi256 {
+                match styles
+                    .underline_color
+                    .as_ref()
+                    .map(|color| self.fit_color(color))
+                {
+                    Some(AnsiColor::Color16 { c16 }) => codes.push(format!("58:5:{}", c16)),
+                    Some(AnsiColor::Color256 { c256 }) => codes.push(format!("58:5:{}", c256)),
+                    Some(AnsiColor::Rgb { r, g, b }) => {
+                        codes.push(format!("58:2::{}:{}:{}", r, g, b))
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        codes
+    }
+
     fn get_icon(&self, config: &SegmentConfig) -> String {
         match self.config.style.mode {
             StyleMode::Plain => config.icon.plain.clone(),
@@ -286,8 +642,13 @@ impl StatusLineGenerator {
         }
     }
 
+    /// Nearest color the terminal can display
+    fn fit_color(&self, color: &AnsiColor) -> AnsiColor {
+        downsample(color, self.color_depth)
+    }
+
     fn apply_color(&self, text: &str, color: Option<&AnsiColor>) -> String {
-        match color {
+        match color.map(|color| self.fit_color(color)).as_ref() {
             Some(AnsiColor::Color16 { c16 }) => {
                 let code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
                 format!("\x1b[{}m{}\x1b[0m", code, text)
@@ -302,16 +663,16 @@ impl StatusLineGenerator {
         }
     }
 
-    fn apply_style(&self, text: &str, color: Option<&AnsiColor>, bold: bool) -> String {
-        let mut codes = Vec::new();
-
-        // Add style codes
-        if bold {
-            codes.push("1".to_string()); // Bold: \x1b[1m
-        }
+    fn apply_style(&self, text: &str, color: Option<&AnsiColor>, attributes: &[String]) -> String {
+        // Extended codes go in their own sequence so parsers that reject colons,
+        // like the TUI preview, still see the color and basic attributes
+        let (extended, mut codes): (Vec<String>, Vec<String>) = attributes
+            .iter()
+            .cloned()
+            .partition(|code| code.contains(':'));
 
         // Add color codes
-        match color {
+        match color.map(|color| self.fit_color(color)).as_ref() {
             Some(AnsiColor::Color16 { c16 }) => {
                 let color_code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
                 codes.push(color_code.to_string());
@@ -331,15 +692,23 @@ impl StatusLineGenerator {
             None => {}
         }
 
-        if codes.is_empty() {
+        let mut prefix = String::new();
+        if !codes.is_empty() {
+            prefix.push_str(&format!("\x1b[{}m", codes.join(";")));
+        }
+        for code in &extended {
+            prefix.push_str(&format!("\x1b[{}m", code));
+        }
+
+        if prefix.is_empty() {
             text.to_string()
         } else {
-            format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+            format!("{}{}\x1b[0m", prefix, text)
         }
     }
 
     fn apply_background_color(&self, color: &AnsiColor) -> String {
-        match color {
+        match &self.fit_color(color) {
             AnsiColor::Color16 { c16 } => {
                 let code = if *c16 < 8 { 40 + c16 } else { 100 + (c16 - 8) };
                 format!("\x1b[{}m", code)
@@ -353,89 +722,74 @@ impl StatusLineGenerator {
         }
     }
 
-    /// Join segments with white separators (non-Powerline)
-    fn join_with_white_separators(&self, rendered_segments: &[String]) -> String {
-        if rendered_segments.is_empty() {
-            return String::new();
-        }
-
-        // Use white color for separator
-        let white_separator = format!("\x1b[37m{}\x1b[0m", self.config.style.separator);
-        rendered_segments.join(&white_separator)
-    }
-
-    /// Join segments with Powerline arrow separators with proper color transitions
-    fn join_with_powerline_arrows(
+    /// Separator between two segments. Powerline glyphs take their colors from
+    /// the neighbouring backgrounds; any other text uses `separator_color`.
+    fn create_separator(
         &self,
-        rendered_segments: &[String],
-        segment_configs: &[(SegmentConfig, SegmentData)],
+        separator: &str,
+        prev: &SegmentConfig,
+        next: &SegmentConfig,
     ) -> String {
-        if rendered_segments.is_empty() {
-            return String::new();
+        let prev_bg = prev.colors.background.as_ref();
+        let next_bg = next.colors.background.as_ref();
+
+        match SeparatorKind::of(separator) {
+            // Solid glyph in the previous background, over the next one
+            SeparatorKind::Right => self.paint(separator, prev_bg, next_bg),
+            // Solid glyph in the next background, over the previous one
+            SeparatorKind::Left => self.paint(separator, next_bg, prev_bg),
+            // Outline glyph inside the previous segment, in its text color by default
+            SeparatorKind::Thin => {
+                let fg = match &self.config.style.separator_color {
+                    Some(SeparatorColor::Color(color)) => Some(color),
+                    Some(SeparatorColor::Inherit) | None => prev.colors.text.as_ref(),
+                };
+                self.paint(separator, fg, prev_bg)
+            }
+            SeparatorKind::Plain => match &self.config.style.separator_color {
+                Some(SeparatorColor::Color(color)) => self.apply_color(separator, Some(color)),
+                Some(SeparatorColor::Inherit) => {
+                    self.apply_color(separator, prev.colors.text.as_ref())
+                }
+                None => format!("\x1b[37m{}\x1b[0m", separator),
+            },
         }
+    }
 
-        if rendered_segments.len() == 1 {
-            return rendered_segments[0].clone();
+    /// Cap drawn before a block, in the background color of its first segment
+    fn start_cap(&self, first: &SegmentConfig) -> String {
+        match &self.config.style.start_cap {
+            Some(cap) => self.paint(cap, first.colors.background.as_ref(), None),
+            None => String::new(),
         }
+    }
 
-        let mut result = rendered_segments[0].clone();
-
-        for (i, _) in rendered_segments.iter().enumerate().skip(1) {
-            let prev_bg = segment_configs
-                .get(i - 1)
-                .and_then(|(config, _)| config.colors.background.as_ref());
-            let curr_bg = segment_configs
-                .get(i)
-                .and_then(|(config, _)| config.colors.background.as_ref());
-
-            // Create Powerline arrow with color transition
-            let arrow = self.create_powerline_arrow(prev_bg, curr_bg);
-
-            result.push_str(&arrow);
-            result.push_str(&rendered_segments[i]);
+    /// Cap drawn after a block, in the background color of its last segment
+    fn end_cap(&self, last: &SegmentConfig) -> String {
+        match &self.config.style.end_cap {
+            Some(cap) => self.paint(cap, last.colors.background.as_ref(), None),
+            None => String::new(),
         }
-
-        // Reset colors at the end
-        result.push_str("\x1b[0m");
-        result
     }
 
-    /// Create a Powerline arrow with proper color transition
-    fn create_powerline_arrow(
-        &self,
-        prev_bg: Option<&AnsiColor>,
-        curr_bg: Option<&AnsiColor>,
-    ) -> String {
-        let arrow_char = "\u{e0b0}";
-
-        match (prev_bg, curr_bg) {
-            (Some(prev), Some(curr)) => {
-                // Arrow foreground = previous segment's background
-                // Arrow background = current segment's background
-                let fg_code = self.color_to_foreground_code(prev);
-                let bg_code = self.apply_background_color(curr);
-                format!("{}{}{}\x1b[0m", bg_code, fg_code, arrow_char)
-            }
-            (Some(prev), None) => {
-                // Previous segment has background, current doesn't
-                let fg_code = self.color_to_foreground_code(prev);
-                format!("{}{}\x1b[0m", fg_code, arrow_char)
-            }
-            (None, Some(curr)) => {
-                // Current segment has background, previous doesn't
-                let bg_code = self.apply_background_color(curr);
-                format!("{}{}\x1b[0m", bg_code, arrow_char)
-            }
-            (None, None) => {
-                // Neither segment has background color
-                arrow_char.to_string()
-            }
+    /// Draw a glyph with optional foreground and background colors
+    fn paint(&self, glyph: &str, fg: Option<&AnsiColor>, bg: Option<&AnsiColor>) -> String {
+        if fg.is_none() && bg.is_none() {
+            return glyph.to_string();
         }
+
+        let fg_code = fg
+            .map(|color| self.color_to_foreground_code(color))
+            .unwrap_or_default();
+        let bg_code = bg
+            .map(|color| self.apply_background_color(color))
+            .unwrap_or_default();
+        format!("{}{}{}\x1b[0m", bg_code, fg_code, glyph)
     }
 
     /// Convert AnsiColor to foreground color code
     fn color_to_foreground_code(&self, color: &AnsiColor) -> String {
-        match color {
+        match &self.fit_color(color) {
             AnsiColor::Color16 { c16 } => {
                 let code = if *c16 < 8 { 30 + c16 } else { 90 + (c16 - 8) };
                 format!("\x1b[{}m", code)
@@ -456,16 +810,65 @@ pub fn collect_all_segments(
 ) -> Vec<(SegmentConfig, SegmentData)> {
     use crate::core::segments::*;
 
+    let context = SegmentContext::new();
     let mut results = Vec::new();
 
     for segment_config in &config.segments {
+        // Disabled segments are never shown, so skip their git calls and file reads
+        if !segment_config.enabled {
+            continue;
+        }
+
         let segment_data = match segment_config.id {
             crate::config::SegmentId::Model => {
-                let segment = ModelSegment::new();
+                let flag = |key: &str| {
+                    segment_config
+                        .options
+                        .get(key)
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false)
+                };
+                let segment = ModelSegment::new()
+                    .with_model_config(context.model_config())
+                    .with_thinking(flag("show_thinking"))
+                    .with_context_variant(flag("show_context_variant"))
+                    .with_fallback(flag("show_fallback"))
+                    .with_provider(
+                        segment_config
+                            .options
+                            .get("show_provider")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(true),
+                    );
                 segment.collect(input)
             }
             crate::config::SegmentId::Directory => {
-                let segment = DirectorySegment::new();
+                let mode = segment_config
+                    .options
+                    .get("mode")
+                    .and_then(|v| v.as_str())
+                    .map(DirectoryMode::from_name)
+                    .unwrap_or_default();
+                let max_length = segment_config
+                    .options
+                    .get("max_length")
+                    .and_then(|v| v.as_u64())
+                    .map(|len| len as usize);
+                let substitutions = segment_config
+                    .options
+                    .get("substitutions")
+                    .and_then(|v| v.as_object())
+                    .map(|map| {
+                        map.iter()
+                            .filter_map(|(from, to)| Some((from.clone(), to.as_str()?.to_string())))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let segment = DirectorySegment::new()
+                    .with_mode(mode)
+                    .with_max_length(max_length)
+                    .with_substitutions(substitutions)
+                    .with_terminal(config.terminal.clone());
                 segment.collect(input)
             }
             crate::config::SegmentId::Git => {
@@ -474,11 +877,40 @@ pub fn collect_all_segments(
                     .get("show_sha")
                     .and_then(|v| v.as_bool())
                     .unwrap_or(false);
-                let segment = GitSegment::new().with_sha(show_sha);
+                let timeout_ms = segment_config
+                    .options
+                    .get("timeout_ms")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(1000);
+                let stale_marker = segment_config
+                    .options
+                    .get("stale_marker")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("~")
+                    .to_string();
+                let backend = segment_config
+                    .options
+                    .get("backend")
+                    .and_then(|v| v.as_str())
+                    .map(GitBackend::from_name)
+                    .unwrap_or_default();
+                let segment = GitSegment::new()
+                    .with_sha(show_sha)
+                    .with_backend(backend)
+                    .with_timeout((timeout_ms > 0).then(|| Duration::from_millis(timeout_ms)))
+                    .with_stale_marker(stale_marker);
                 segment.collect(input)
             }
             crate::config::SegmentId::Usage => {
-                let segment = UsageSegment::new();
+                let segment = UsageSegment::new()
+                    .with_model_config(context.model_config())
+                    .with_provider(
+                        segment_config
+                            .options
+                            .get("show_provider")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(true),
+                    );
                 segment.collect(input)
             }
             crate::config::SegmentId::Cost => {
@@ -486,7 +918,13 @@ pub fn collect_all_segments(
                 segment.collect(input)
             }
             crate::config::SegmentId::Session => {
-                let segment = SessionSegment::new();
+                let mode = segment_config
+                    .options
+                    .get("mode")
+                    .and_then(|v| v.as_str())
+                    .map(SessionMode::from_name)
+                    .unwrap_or_default();
+                let segment = SessionSegment::new().with_mode(mode);
                 segment.collect(input)
             }
             crate::config::SegmentId::OutputStyle => {
@@ -497,12 +935,332 @@ pub fn collect_all_segments(
                 let segment = UpdateSegment::new();
                 segment.collect(input)
             }
+            crate::config::SegmentId::Commit => {
+                let show_author = segment_config
+                    .options
+                    .get("show_author")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                let show_session_commits = segment_config
+                    .options
+                    .get("show_session_commits")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let timeout_ms = segment_config
+                    .options
+                    .get("timeout_ms")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(1000);
+                let segment = CommitSegment::new()
+                    .with_author(show_author)
+                    .with_session_commits(show_session_commits)
+                    .with_timeout((timeout_ms > 0).then(|| Duration::from_millis(timeout_ms)));
+                segment.collect(input)
+            }
+            crate::config::SegmentId::PullRequest => {
+                let cache_file = segment_config
+                    .options
+                    .get("cache_file")
+                    .and_then(|v| v.as_str())
+                    .map(|path| match path.strip_prefix("~/") {
+                        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+                        None => PathBuf::from(path),
+                    });
+                let show_forge = segment_config
+                    .options
+                    .get("show_forge")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
+                let timeout_ms = segment_config
+                    .options
+                    .get("timeout_ms")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(1000);
+                let segment = PullRequestSegment::new()
+                    .with_cache_file(cache_file)
+                    .with_forge(show_forge)
+                    .with_timeout((timeout_ms > 0).then(|| Duration::from_millis(timeout_ms)));
+                segment.collect(input)
+            }
+            crate::config::SegmentId::Project => {
+                let show_version = segment_config
+                    .options
+                    .get("show_version")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let type_icons = segment_config
+                    .options
+                    .get("type_icons")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let segment = ProjectSegment::new()
+                    .with_version(show_version)
+                    .with_type_icons(type_icons);
+                segment.collect(input)
+            }
+            crate::config::SegmentId::Environment => {
+                let show_venv = segment_config
+                    .options
+                    .get("show_venv")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let show_conda = segment_config
+                    .options
+                    .get("show_conda")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let show_container = segment_config
+                    .options
+                    .get("show_container")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let show_ssh = segment_config
+                    .options
+                    .get("show_ssh")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let show_nix = segment_config
+                    .options
+                    .get("show_nix")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let segment = EnvironmentSegment::new()
+                    .with_venv(show_venv)
+                    .with_conda(show_conda)
+                    .with_container(show_container)
+                    .with_ssh(show_ssh)
+                    .with_nix(show_nix);
+                segment.collect(input)
+            }
+            crate::config::SegmentId::System => {
+                let options = &segment_config.options;
+                let flag = |key: &str| options.get(key).and_then(|v| v.as_bool()).unwrap_or(true);
+                let thresholds = |prefix: &str, warning: f64, critical: f64| {
+                    Thresholds::new(
+                        options
+                            .get(&format!("{}_warning", prefix))
+                            .and_then(|v| v.as_f64())
+                            .unwrap_or(warning),
+                        options
+                            .get(&format!("{}_critical", prefix))
+                            .and_then(|v| v.as_f64())
+                            .unwrap_or(critical),
+                    )
+                };
+                let segment = SystemSegment::new()
+                    .with_load(flag("show_load"), thresholds("load", 1.0, 2.0))
+                    .with_memory(flag("show_memory"), thresholds("memory", 80.0, 95.0))
+                    .with_disk(flag("show_disk"), thresholds("disk", 90.0, 98.0));
+                segment.collect(input)
+            }
+            crate::config::SegmentId::Clock => {
+                let format = segment_config
+                    .options
+                    .get("format")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("%H:%M");
+                let utc_offset = segment_config
+                    .options
+                    .get("utc_offset")
+                    .and_then(|v| v.as_str());
+                let show_clock = segment_config
+                    .options
+                    .get("show_clock")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let show_idle = segment_config
+                    .options
+                    .get("show_idle")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(true);
+                let idle_threshold_secs = segment_config
+                    .options
+                    .get("idle_threshold_secs")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(60);
+                let segment = ClockSegment::new()
+                    .with_format(format)
+                    .with_utc_offset(utc_offset)
+                    .with_clock(show_clock)
+                    .with_idle(show_idle, idle_threshold_secs);
+                segment.collect(input)
+            }
+            crate::config::SegmentId::Productivity => {
+                let metrics = segment_config
+                    .options
+                    .get("metrics")
+                    .and_then(|v| v.as_array())
+                    .map(|names| {
+                        names
+                            .iter()
+                            .filter_map(|name| name.as_str())
+                            .filter_map(ProductivityMetric::from_name)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let segment = ProductivitySegment::new().with_metrics(metrics);
+                segment.collect(input)
+            }
         };
 
         if let Some(data) = segment_data {
-            results.push((segment_config.clone(), data));
+            let mut segment_config = segment_config.clone();
+            // Segments may pick a more specific icon at runtime, e.g. per project type
+            if let Some(icon) = &data.overrides.icon {
+                segment_config.icon = icon.clone();
+            }
+            // Explicit color overrides, e.g. a provider color from models.toml
+            if let Some(color) = &data.overrides.color {
+                segment_config.colors.icon = Some(color.clone());
+                segment_config.colors.text = Some(color.clone());
+            }
+            // Threshold-based segments report a level that recolors their text
+            let level_color = match data.overrides.level {
+                ResourceLevel::Warning => Some(("warning_color", AnsiColor::Color16 { c16: 11 })),
+                ResourceLevel::Critical => Some(("critical_color", AnsiColor::Color16 { c16: 9 })),
+                ResourceLevel::Normal => None,
+            };
+            if let Some((key, default)) = level_color {
+                let color = segment_config
+                    .options
+                    .get(key)
+                    .and_then(|v| serde_json::from_value(v.clone()).ok())
+                    .unwrap_or(default);
+                segment_config.colors.text = Some(color);
+            }
+            results.push((segment_config, data));
         }
     }
 
     results
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{
+        ColorConfig, IconConfig, SegmentId, SegmentLayout, StyleConfig, TerminalConfig,
+    };
+    use std::collections::HashMap;
+
+    fn generator(width: usize) -> StatusLineGenerator {
+        StatusLineGenerator::new(Config {
+            style: StyleConfig {
+                mode: StyleMode::Plain,
+                separator: " | ".to_string(),
+                start_cap: None,
+                end_cap: None,
+                separator_color: None,
+                roles: Default::default(),
+            },
+            segments: Vec::new(),
+            theme: "test".to_string(),
+            rows: Vec::new(),
+            terminal: TerminalConfig {
+                width: Some(width),
+                color_depth: Some(ColorDepth::TrueColor),
+                ..Default::default()
+            },
+        })
+    }
+
+    fn segment(
+        id: SegmentId,
+        primary: &str,
+        secondary: &str,
+        layout: SegmentLayout,
+    ) -> (SegmentConfig, SegmentData) {
+        let config = SegmentConfig {
+            id,
+            enabled: true,
+            icon: IconConfig {
+                plain: "@".to_string(),
+                nerd_font: "@".to_string(),
+            },
+            colors: ColorConfig {
+                icon: None,
+                text: None,
+                background: None,
+                secondary: None,
+            },
+            styles: TextStyleConfig::default(),
+            layout,
+            options: HashMap::new(),
+        };
+        let data = SegmentData {
+            primary: primary.to_string(),
+            primary_spans: Vec::new(),
+            secondary: secondary.to_string(),
+            secondary_spans: Vec::new(),
+            overrides: Default::default(),
+            metadata: HashMap::new(),
+        };
+        (config, data)
+    }
+
+    fn priority(priority: u8) -> SegmentLayout {
+        SegmentLayout {
+            priority,
+            ..Default::default()
+        }
+    }
+
+    fn render(width: usize, segments: &[(SegmentConfig, SegmentData)]) -> String {
+        strip_ansi(&generator(width).generate_row(segments, " | "))
+    }
+
+    #[test]
+    fn lowest_priority_gives_way_first() {
+        let segments = [
+            segment(SegmentId::Model, "alpha", "", priority(2)),
+            segment(SegmentId::Directory, "beta", "", priority(0)),
+            segment(SegmentId::Git, "gamma", "", priority(1)),
+        ];
+        assert_eq!(render(26, &segments), "@ alpha | @ beta | @ gamma");
+        assert_eq!(render(20, &segments), "@ alpha | @ gamma");
+        assert_eq!(render(10, &segments), "@ alpha");
+    }
+
+    #[test]
+    fn ties_give_way_from_the_end_of_the_line() {
+        let segments = [
+            segment(SegmentId::Model, "alpha", "", priority(0)),
+            segment(SegmentId::Directory, "beta", "", priority(0)),
+            segment(SegmentId::Git, "gamma", "", priority(0)),
+        ];
+        assert_eq!(render(20, &segments), "@ alpha | @ beta");
+    }
+
+    #[test]
+    fn segments_shorten_before_they_are_dropped() {
+        let segments = [
+            segment(SegmentId::Directory, "alpha", "", priority(1)),
+            segment(SegmentId::Model, "claude-sonnet", "(200k)", priority(0)),
+        ];
+        // Secondary text goes first
+        assert_eq!(render(32, &segments), "@ alpha | @ claude-sonnet (200k)");
+        assert_eq!(render(25, &segments), "@ alpha | @ claude-sonnet");
+        // Then the primary text is cut with an ellipsis
+        assert_eq!(render(22, &segments), "@ alpha | @ claude-so…");
+        // Then the segment is dropped rather than cut below the minimum
+        assert_eq!(render(16, &segments), "@ alpha");
+    }
+
+    #[test]
+    fn right_block_is_padded_to_the_terminal_width() {
+        let right = SegmentLayout {
+            align: Alignment::Right,
+            ..Default::default()
+        };
+        let segments = [
+            segment(SegmentId::Cost, "beta", "", right),
+            segment(SegmentId::Model, "alpha", "", SegmentLayout::default()),
+        ];
+        let line = render(20, &segments);
+        assert_eq!(line, "@ alpha       @ beta");
+        assert_eq!(line.chars().count(), 20);
+
+        // A full line keeps one column between the blocks
+        assert_eq!(render(14, &segments), "@ alpha @ beta");
+    }
+}
@@ -1,6 +1,6 @@
 use ccometixline::cli::Cli;
-use ccometixline::config::{Config, InputData};
-use ccometixline::core::{collect_all_segments, StatusLineGenerator};
+use ccometixline::config::{ColorDepth, Config, InputData};
+use ccometixline::core::{collect_all_segments, OutputFormat, StatusLineGenerator};
 use ccometixline::ui::run_intro;
 
 use std::io::{self, IsTerminal};
@@ -29,6 +29,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     if cli.check {
         let config = Config::load()?;
         config.check()?;
+        if let Err(e) = ccometixline::config::ModelConfig::check() {
+            eprintln!("✗ Model configuration invalid\n{}", e);
+            std::process::exit(1);
+        }
         println!("✓ Configuration valid");
         return Ok(());
     }
@@ -105,6 +109,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         config = ccometixline::ui::themes::ThemePresets::get_theme(&theme);
     }
 
+    // Apply color depth override if provided; "auto" forces detection
+    if let Some(color) = cli.color.as_deref() {
+        config.terminal.color_depth = ColorDepth::from_name(color);
+    }
+
     // Check if stdin has data
     if io::stdin().is_terminal() {
         // No input data available, show main menu
@@ -148,8 +157,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Collect segment data
     let segments_data = collect_all_segments(&config, &input);
 
-        // Render statusline
-    let generator = StatusLineGenerator::new(config);
+    // Render statusline
+    let format = cli
+        .format
+        .as_deref()
+        .map(OutputFormat::from_name)
+        .unwrap_or_default();
+    let generator = StatusLineGenerator::new(config).with_format(format);
     let statusline = generator.generate(segments_data);
 
     println!("{}", statusline);
@@ -1,4 +1,4 @@
-use crate::config::{Config, SegmentId, StyleMode};
+use crate::config::{Config, SegmentId, StyleMode, StyleTarget, TextAttribute};
 use crate::ui::components::{
     color_picker::{ColorPickerComponent, NavDirection},
     help::HelpComponent,
@@ -120,16 +120,12 @@ impl App {
                     match key.code {
                         KeyCode::Esc => app.separator_editor.close(),
                         KeyCode::Enter => {
-                            let new_separator = app.separator_editor.get_separator();
-                            app.config.style.separator = new_separator;
+                            app.separator_editor.apply_to(&mut app.config.style);
                             app.separator_editor.close();
                             app.preview.update_preview(&app.config);
                             app.status_message = Some("Separator updated!".to_string());
                         }
-                        KeyCode::Tab => {
-                            app.separator_editor.input.clear();
-                            app.separator_editor.selected_preset = None;
-                        }
+                        KeyCode::Tab => app.separator_editor.clear(),
                         KeyCode::Up => app.separator_editor.move_preset_selection(-1),
                         KeyCode::Down => app.separator_editor.move_preset_selection(1),
                         KeyCode::Char(c) => app.separator_editor.input_char(c),
@@ -217,6 +213,8 @@ impl App {
                                 app.move_selection(1);
                             }
                         }
+                        KeyCode::Left => app.move_style_attribute(-1),
+                        KeyCode::Right => app.move_style_attribute(1),
                         KeyCode::Enter => app.toggle_current(),
                         KeyCode::Tab => app.switch_panel(),
                         KeyCode::Char('1') => app.switch_to_theme("default"),
@@ -226,6 +224,7 @@ impl App {
                         KeyCode::Char('p') => app.cycle_theme(),
                         KeyCode::Char('r') => app.reset_to_theme_defaults(),
                         KeyCode::Char('e') | KeyCode::Char('E') => app.open_separator_editor(),
+                        KeyCode::Char('l') | KeyCode::Char('L') => app.cycle_segment_row(),
                         _ => {}
                     }
                 }
@@ -303,6 +302,8 @@ impl App {
                 "[Tab] Switch Panel",
                 "[Enter] Toggle/Edit",
                 "[Shift+↑↓] Reorder",
+                "[←→] Style",
+                "[L] Line",
                 "[1-4] Theme",
                 "[P] Switch Theme",
                 "[R] Reset",
@@ -462,15 +463,18 @@ impl App {
                 self.selected_segment = new_selection;
             }
             Panel::Settings => {
-                let field_count = 7; // Enabled, Icon, IconColor, TextColor, TextStyle, BackgroundColor, Options
+                let field_count = 10; // Enabled, Icon, IconColor, TextColor, SecondaryColor, BackgroundColor, IconStyle, TextStyle, SecondaryStyle, Options
                 let current_field = match self.selected_field {
                     FieldSelection::Enabled => 0i32,
                     FieldSelection::Icon => 1,
                     FieldSelection::IconColor => 2,
                     FieldSelection::TextColor => 3,
-                    FieldSelection::BackgroundColor => 4,
-                    FieldSelection::TextStyle => 5,
-                    FieldSelection::Options => 6,
+                    FieldSelection::SecondaryColor => 4,
+                    FieldSelection::BackgroundColor => 5,
+                    FieldSelection::IconStyle => 6,
+                    FieldSelection::TextStyle => 7,
+                    FieldSelection::SecondaryStyle => 8,
+                    FieldSelection::Options => 9,
                 };
                 let new_field = (current_field + delta).clamp(0, field_count - 1) as usize;
                 self.selected_field = match new_field {
@@ -478,9 +482,12 @@ impl App {
                     1 => FieldSelection::Icon,
                     2 => FieldSelection::IconColor,
                     3 => FieldSelection::TextColor,
-                    4 => FieldSelection::BackgroundColor,
-                    5 => FieldSelection::TextStyle,
-                    6 => FieldSelection::Options,
+                    4 => FieldSelection::SecondaryColor,
+                    5 => FieldSelection::BackgroundColor,
+                    6 => FieldSelection::IconStyle,
+                    7 => FieldSelection::TextStyle,
+                    8 => FieldSelection::SecondaryStyle,
+                    9 => FieldSelection::Options,
                     _ => FieldSelection::Enabled,
                 };
             }
@@ -502,6 +509,13 @@ impl App {
                         SegmentId::Session => "Session",
                         SegmentId::OutputStyle => "Output Style",
                         SegmentId::Update => "Update",
+                        SegmentId::Commit => "Commit",
+                        SegmentId::PullRequest => "Pull Request",
+                        SegmentId::Project => "Project",
+                        SegmentId::Environment => "Environment",
+                        SegmentId::System => "System",
+                        SegmentId::Clock => "Clock",
+                        SegmentId::Productivity => "Productivity",
                     };
                     let is_enabled = segment.enabled;
                     self.status_message = Some(format!(
@@ -528,6 +542,13 @@ impl App {
                                 SegmentId::Session => "Session",
                                 SegmentId::OutputStyle => "Output Style",
                                 SegmentId::Update => "Update",
+                                SegmentId::Commit => "Commit",
+                                SegmentId::PullRequest => "Pull Request",
+                                SegmentId::Project => "Project",
+                                SegmentId::Environment => "Environment",
+                                SegmentId::System => "System",
+                                SegmentId::Clock => "Clock",
+                                SegmentId::Productivity => "Productivity",
                             };
                             let is_enabled = segment.enabled;
                             self.status_message = Some(format!(
@@ -541,21 +562,12 @@ impl App {
                     FieldSelection::Icon => self.open_icon_selector(),
                     FieldSelection::IconColor
                     | FieldSelection::TextColor
+                    | FieldSelection::SecondaryColor
                     | FieldSelection::BackgroundColor => self.open_color_picker(),
-                    FieldSelection::TextStyle => {
-                        // Toggle text bold style
-                        if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
-                            segment.styles.text_bold = !segment.styles.text_bold;
-                            self.status_message = Some(format!(
-                                "Text bold {}",
-                                if segment.styles.text_bold {
-                                    "enabled"
-                                } else {
-                                    "disabled"
-                                }
-                            ));
-                            self.preview.update_preview(&self.config);
-                        }
+                    FieldSelection::IconStyle => self.toggle_style_attribute(StyleTarget::Icon),
+                    FieldSelection::TextStyle => self.toggle_style_attribute(StyleTarget::Text),
+                    FieldSelection::SecondaryStyle => {
+                        self.toggle_style_attribute(StyleTarget::Secondary)
                     }
                     FieldSelection::Options => {
                         // TODO: Implement options editor
@@ -567,6 +579,40 @@ impl App {
         }
     }
 
+    /// Move the focused attribute on the icon and text style fields
+    fn move_style_attribute(&mut self, delta: i32) {
+        if self.selected_panel == Panel::Settings
+            && matches!(
+                self.selected_field,
+                FieldSelection::IconStyle
+                    | FieldSelection::TextStyle
+                    | FieldSelection::SecondaryStyle
+            )
+        {
+            let last = TextAttribute::ALL.len() as i32 - 1;
+            self.settings.selected_attribute =
+                (self.settings.selected_attribute as i32 + delta).clamp(0, last) as usize;
+        }
+    }
+
+    fn toggle_style_attribute(&mut self, target: StyleTarget) {
+        let attribute = TextAttribute::ALL[self.settings.selected_attribute];
+        if let Some(segment) = self.config.segments.get_mut(self.selected_segment) {
+            let enabled = segment.styles.toggle(target, attribute);
+            self.status_message = Some(format!(
+                "{} {} {}",
+                match target {
+                    StyleTarget::Icon => "Icon",
+                    StyleTarget::Text => "Text",
+                    StyleTarget::Secondary => "Secondary",
+                },
+                attribute.name().to_lowercase(),
+                if enabled { "enabled" } else { "disabled" }
+            ));
+            self.preview.update_preview(&self.config);
+        }
+    }
+
     fn switch_panel(&mut self) {
         self.selected_panel = match self.selected_panel {
             Panel::SegmentList => Panel::Settings,
@@ -578,6 +624,7 @@ impl App {
         if self.selected_panel == Panel::Settings
             && (self.selected_field == FieldSelection::IconColor
                 || self.selected_field == FieldSelection::TextColor
+                || self.selected_field == FieldSelection::SecondaryColor
                 || self.selected_field == FieldSelection::BackgroundColor)
         {
             self.color_picker.open();
@@ -595,6 +642,7 @@ impl App {
             match self.selected_field {
                 FieldSelection::IconColor => segment.colors.icon = Some(color),
                 FieldSelection::TextColor => segment.colors.text = Some(color),
+                FieldSelection::SecondaryColor => segment.colors.secondary = Some(color),
                 FieldSelection::BackgroundColor => segment.colors.background = Some(color),
                 _ => {}
             }
@@ -649,6 +697,8 @@ impl App {
         if self.selected_panel == Panel::SegmentList && self.selected_segment > 0 {
             let current_idx = self.selected_segment;
             self.config.segments.swap(current_idx, current_idx - 1);
+            self.config
+                .place_in_row(self.config.segments[current_idx - 1].id);
             self.selected_segment -= 1;
             self.preview.update_preview(&self.config);
             self.status_message = Some("Moved segment up".to_string());
@@ -662,12 +712,52 @@ impl App {
         {
             let current_idx = self.selected_segment;
             self.config.segments.swap(current_idx, current_idx + 1);
+            self.config
+                .place_in_row(self.config.segments[current_idx + 1].id);
             self.selected_segment += 1;
             self.preview.update_preview(&self.config);
             self.status_message = Some("Moved segment down".to_string());
         }
     }
 
+    /// Move the currently selected segment to the next line of the statusline,
+    /// opening a new line after the last one and wrapping back to the first
+    fn cycle_segment_row(&mut self) {
+        if self.selected_panel != Panel::SegmentList {
+            return;
+        }
+        let Some(id) = self
+            .config
+            .segments
+            .get(self.selected_segment)
+            .map(|s| s.id)
+        else {
+            return;
+        };
+
+        let current = self.config.row_of(id);
+        let row_count = self.config.rows.len().max(1);
+        // Moving a segment that is alone on the last line would only recreate that line
+        let alone = current > 0
+            && self
+                .config
+                .rows
+                .get(current)
+                .is_some_and(|row| row.segments == [id]);
+        let next = if current + 1 < row_count || (current + 1 == row_count && !alone) {
+            current + 1
+        } else {
+            0
+        };
+
+        self.config.set_row(id, next);
+        self.preview.update_preview(&self.config);
+        self.status_message = Some(format!(
+            "Moved segment to line {}",
+            self.config.row_of(id) + 1
+        ));
+    }
+
     /// Write current config to the current theme file
     fn write_to_current_theme(&mut self) {
         let current_theme = &self.config.theme;
@@ -699,6 +789,6 @@ impl App {
     /// Open separator editor with current separator
     fn open_separator_editor(&mut self) {
         self.status_message = Some("Opening separator editor...".to_string());
-        self.separator_editor.open(&self.config.style.separator);
+        self.separator_editor.open(&self.config.style);
     }
 }
@@ -42,6 +42,8 @@ impl HelpComponent {
                 ("[Tab]", "Switch Panel"),
                 ("[Enter]", "Toggle/Edit"),
                 ("[Shift+↑↓]", "Reorder"),
+                ("[←→]", "Style"),
+                ("[L]", "Line"),
                 ("[1-4]", "Theme"),
                 ("[P]", "Switch Theme"),
                 ("[R]", "Reset"),
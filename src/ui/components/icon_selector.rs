@@ -1,4 +1,5 @@
 use crate::config::StyleMode;
+use crate::core::segments::project::ProjectType;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
@@ -340,8 +341,19 @@ struct IconInfo {
     name: &'static str,
 }
 
+/// Per-language icons, the same glyphs the Project segment shows for each project type
+fn project_type_icons(nerd_font: bool) -> impl Iterator<Item = IconInfo> {
+    ProjectType::ALL.into_iter().map(move |project_type| {
+        let (plain, nerd) = project_type.icons();
+        IconInfo {
+            icon: if nerd_font { nerd } else { plain },
+            name: project_type.label(),
+        }
+    })
+}
+
 fn get_plain_icons() -> Vec<IconInfo> {
-    vec![
+    let icons = vec![
         IconInfo {
             icon: "🤖",
             name: "Robot (Model)",
@@ -438,11 +450,12 @@ fn get_plain_icons() -> Vec<IconInfo> {
             icon: "◄",
             name: "Left Triangle",
         },
-    ]
+    ];
+    icons.into_iter().chain(project_type_icons(false)).collect()
 }
 
 fn get_nerd_font_icons() -> Vec<IconInfo> {
-    vec![
+    let icons = vec![
         IconInfo {
             icon: "\u{e26d}",
             name: "Robot (Model)",
@@ -523,7 +536,8 @@ fn get_nerd_font_icons() -> Vec<IconInfo> {
             icon: "\u{f31b}",
             name: "Github",
         },
-    ]
+    ];
+    icons.into_iter().chain(project_type_icons(true)).collect()
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -1,5 +1,5 @@
-use crate::config::{Config, SegmentId};
-use crate::core::segments::SegmentData;
+use crate::config::{Config, SegmentId, SpanRole};
+use crate::core::segments::{SegmentData, TextSpan};
 use crate::core::StatusLineGenerator;
 use ratatui::{
     layout::Rect,
@@ -97,7 +97,10 @@ impl PreviewComponent {
             let mock_data = match segment_config.id {
                 SegmentId::Model => SegmentData {
                     primary: "Sonnet 4".to_string(),
+                    primary_spans: Vec::new(),
                     secondary: "".to_string(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
                     metadata: {
                         let mut map = HashMap::new();
                         map.insert("model".to_string(), "claude-4-sonnet-20250512".to_string());
@@ -106,7 +109,10 @@ impl PreviewComponent {
                 },
                 SegmentId::Directory => SegmentData {
                     primary: "CCometixLine".to_string(),
+                    primary_spans: Vec::new(),
                     secondary: "".to_string(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
                     metadata: {
                         let mut map = HashMap::new();
                         map.insert("current_dir".to_string(), "~/CCometixLine".to_string());
@@ -115,19 +121,30 @@ impl PreviewComponent {
                 },
                 SegmentId::Git => SegmentData {
                     primary: "master".to_string(),
-                    secondary: "✓".to_string(),
+                    primary_spans: Vec::new(),
+                    secondary: String::new(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
                     metadata: {
                         let mut map = HashMap::new();
                         map.insert("branch".to_string(), "master".to_string());
-                        map.insert("status".to_string(), "Clean".to_string());
-                        map.insert("ahead".to_string(), "0".to_string());
+                        map.insert("status".to_string(), "Dirty".to_string());
+                        map.insert("ahead".to_string(), "2".to_string());
                         map.insert("behind".to_string(), "0".to_string());
                         map
                     },
-                },
+                }
+                .with_secondary_spans(vec![
+                    TextSpan::with_role("●", SpanRole::Warning),
+                    TextSpan::plain(" "),
+                    TextSpan::with_role("↑2", SpanRole::Value),
+                ]),
                 SegmentId::Usage => SegmentData {
-                    primary: "78.2%".to_string(),
-                    secondary: "· 156.4k".to_string(),
+                    primary: String::new(),
+                    primary_spans: Vec::new(),
+                    secondary: String::new(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
                     metadata: {
                         let mut map = HashMap::new();
                         map.insert("total_tokens".to_string(), "156400".to_string());
@@ -135,10 +152,19 @@ impl PreviewComponent {
                         map.insert("session_tokens".to_string(), "48200".to_string());
                         map
                     },
-                },
+                }
+                .with_primary_spans(vec![
+                    TextSpan::with_role("78.2%", SpanRole::Value),
+                    TextSpan::with_role(" · ", SpanRole::Muted),
+                    TextSpan::with_role("156.4k", SpanRole::Value),
+                    TextSpan::with_role(" tokens", SpanRole::Unit),
+                ]),
                 SegmentId::Cost => SegmentData {
                     primary: "$0.02".to_string(),
+                    primary_spans: Vec::new(),
                     secondary: "".to_string(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
                     metadata: {
                         let mut map = HashMap::new();
                         map.insert("cost".to_string(), "0.01234".to_string());
@@ -147,7 +173,10 @@ impl PreviewComponent {
                 },
                 SegmentId::Session => SegmentData {
                     primary: "3m45s".to_string(),
-                    secondary: "+156 -23".to_string(),
+                    primary_spans: Vec::new(),
+                    secondary: String::new(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
                     metadata: {
                         let mut map = HashMap::new();
                         map.insert("duration_ms".to_string(), "225000".to_string());
@@ -155,10 +184,18 @@ impl PreviewComponent {
                         map.insert("lines_removed".to_string(), "23".to_string());
                         map
                     },
-                },
+                }
+                .with_secondary_spans(vec![
+                    TextSpan::with_role("+156", SpanRole::Positive),
+                    TextSpan::plain(" "),
+                    TextSpan::with_role("-23", SpanRole::Negative),
+                ]),
                 SegmentId::OutputStyle => SegmentData {
                     primary: "default".to_string(),
+                    primary_spans: Vec::new(),
                     secondary: "".to_string(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
                     metadata: {
                         let mut map = HashMap::new();
                         map.insert("style_name".to_string(), "default".to_string());
@@ -167,7 +204,10 @@ impl PreviewComponent {
                 },
                 SegmentId::Update => SegmentData {
                     primary: format!("v{}", env!("CARGO_PKG_VERSION")),
+                    primary_spans: Vec::new(),
                     secondary: "".to_string(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
                     metadata: {
                         let mut map = HashMap::new();
                         map.insert(
@@ -178,6 +218,97 @@ impl PreviewComponent {
                         map
                     },
                 },
+                SegmentId::Commit => SegmentData {
+                    primary: "12m".to_string(),
+                    primary_spans: Vec::new(),
+                    secondary: "2 new".to_string(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("age_secs".to_string(), "720".to_string());
+                        map.insert("session_commits".to_string(), "2".to_string());
+                        map
+                    },
+                },
+                SegmentId::Project => SegmentData {
+                    primary: "rust 1.80".to_string(),
+                    primary_spans: Vec::new(),
+                    secondary: "".to_string(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("type".to_string(), "rust".to_string());
+                        map.insert("version".to_string(), "1.80".to_string());
+                        map
+                    },
+                },
+                SegmentId::Environment => SegmentData {
+                    primary: "venv:crate ssh".to_string(),
+                    primary_spans: Vec::new(),
+                    secondary: "".to_string(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("venv".to_string(), "crate".to_string());
+                        map.insert("ssh".to_string(), "true".to_string());
+                        map
+                    },
+                },
+                SegmentId::System => SegmentData {
+                    primary: "load 0.42 · mem 9.1G · disk 41G".to_string(),
+                    primary_spans: Vec::new(),
+                    secondary: "".to_string(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("load1".to_string(), "0.42".to_string());
+                        map.insert("level".to_string(), "normal".to_string());
+                        map
+                    },
+                },
+                SegmentId::Clock => SegmentData {
+                    primary: "14:32".to_string(),
+                    primary_spans: Vec::new(),
+                    secondary: "idle 5m".to_string(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("time".to_string(), "14:32".to_string());
+                        map.insert("idle_secs".to_string(), "300".to_string());
+                        map
+                    },
+                },
+                SegmentId::Productivity => SegmentData {
+                    primary: "+80 net · 60.0/min · $0.42/100".to_string(),
+                    primary_spans: Vec::new(),
+                    secondary: "".to_string(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("net_lines".to_string(), "80".to_string());
+                        map.insert("lines_per_min".to_string(), "60.00".to_string());
+                        map
+                    },
+                },
+                SegmentId::PullRequest => SegmentData {
+                    primary: "PR #123".to_string(),
+                    primary_spans: Vec::new(),
+                    secondary: "".to_string(),
+                    secondary_spans: Vec::new(),
+                    overrides: Default::default(),
+                    metadata: {
+                        let mut map = HashMap::new();
+                        map.insert("number".to_string(), "123".to_string());
+                        map.insert("forge".to_string(), "GitHub".to_string());
+                        map
+                    },
+                },
             };
 
             segments_data.push((segment_config.clone(), mock_data));
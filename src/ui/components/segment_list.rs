@@ -19,8 +19,11 @@ pub enum FieldSelection {
     Icon,
     IconColor,
     TextColor,
+    SecondaryColor,
     BackgroundColor,
+    IconStyle,
     TextStyle,
+    SecondaryStyle,
     Options,
 }
 
@@ -56,6 +59,19 @@ impl SegmentListComponent {
                     SegmentId::Session => "Session",
                     SegmentId::OutputStyle => "Output Style",
                     SegmentId::Update => "Update",
+                    SegmentId::Commit => "Commit",
+                    SegmentId::PullRequest => "Pull Request",
+                    SegmentId::Project => "Project",
+                    SegmentId::Environment => "Environment",
+                    SegmentId::System => "System",
+                    SegmentId::Clock => "Clock",
+                    SegmentId::Productivity => "Productivity",
+                };
+                // Only show row numbers once a multi-line layout exists
+                let row_marker = if config.rows.is_empty() {
+                    String::new()
+                } else {
+                    format!(" [{}]", config.row_of(segment.id) + 1)
                 };
 
                 if is_selected {
@@ -63,10 +79,14 @@ impl SegmentListComponent {
                     ListItem::new(Line::from(vec![
                         Span::styled("▶ ", Style::default().fg(Color::Cyan)),
                         Span::raw(format!("{} {}", enabled_marker, segment_name)),
+                        Span::styled(row_marker, Style::default().fg(Color::DarkGray)),
                     ]))
                 } else {
                     // Non-selected item
-                    ListItem::new(format!("  {} {}", enabled_marker, segment_name))
+                    ListItem::new(Line::from(vec![
+                        Span::raw(format!("  {} {}", enabled_marker, segment_name)),
+                        Span::styled(row_marker, Style::default().fg(Color::DarkGray)),
+                    ]))
                 }
             })
             .collect();
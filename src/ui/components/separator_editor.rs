@@ -1,3 +1,4 @@
+use crate::config::StyleConfig;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
@@ -9,6 +10,8 @@ use ratatui::{
 pub struct SeparatorEditorComponent {
     pub is_open: bool,
     pub input: String,
+    pub start_cap: Option<String>,
+    pub end_cap: Option<String>,
     pub presets: Vec<SeparatorPreset>,
     pub selected_preset: Option<usize>,
 }
@@ -17,6 +20,8 @@ pub struct SeparatorEditorComponent {
 pub struct SeparatorPreset {
     pub name: String,
     pub value: String,
+    pub start_cap: Option<String>,
+    pub end_cap: Option<String>,
     pub description: String,
 }
 
@@ -31,6 +36,8 @@ impl SeparatorEditorComponent {
         Self {
             is_open: false,
             input: String::new(),
+            start_cap: None,
+            end_cap: None,
             presets: Self::default_presets(),
             selected_preset: None,
         }
@@ -41,39 +48,89 @@ impl SeparatorEditorComponent {
             SeparatorPreset {
                 name: "Pipe".to_string(),
                 value: " | ".to_string(),
+                start_cap: None,
+                end_cap: None,
                 description: "Classic pipe separator".to_string(),
             },
             SeparatorPreset {
                 name: "Thin".to_string(),
                 value: " │ ".to_string(),
+                start_cap: None,
+                end_cap: None,
                 description: "Thin vertical line".to_string(),
             },
             SeparatorPreset {
                 name: "Arrow".to_string(),
                 value: "\u{e0b0}".to_string(),
+                start_cap: None,
+                end_cap: None,
                 description: "Powerline arrow (seamless transition)".to_string(),
             },
+            SeparatorPreset {
+                name: "Arrow Capped".to_string(),
+                value: "\u{e0b0}".to_string(),
+                start_cap: None,
+                end_cap: Some("\u{e0b0}".to_string()),
+                description: "Powerline arrow with a closing arrow".to_string(),
+            },
+            SeparatorPreset {
+                name: "Rounded".to_string(),
+                value: "\u{e0b4}".to_string(),
+                start_cap: Some("\u{e0b6}".to_string()),
+                end_cap: Some("\u{e0b4}".to_string()),
+                description: "Rounded caps and transitions".to_string(),
+            },
+            SeparatorPreset {
+                name: "Slanted".to_string(),
+                value: "\u{e0bc}".to_string(),
+                start_cap: Some("\u{e0ba}".to_string()),
+                end_cap: Some("\u{e0bc}".to_string()),
+                description: "Slanted caps and transitions".to_string(),
+            },
+            SeparatorPreset {
+                name: "Left Arrow".to_string(),
+                value: "\u{e0b2}".to_string(),
+                start_cap: Some("\u{e0b2}".to_string()),
+                end_cap: None,
+                description: "Left-facing Powerline arrows".to_string(),
+            },
+            SeparatorPreset {
+                name: "Thin Arrow".to_string(),
+                value: "\u{e0b1}".to_string(),
+                start_cap: None,
+                end_cap: None,
+                description: "Same-color divider on the segment background".to_string(),
+            },
             SeparatorPreset {
                 name: "Space".to_string(),
                 value: "  ".to_string(),
+                start_cap: None,
+                end_cap: None,
                 description: "Double space".to_string(),
             },
             SeparatorPreset {
                 name: "Dot".to_string(),
                 value: " • ".to_string(),
+                start_cap: None,
+                end_cap: None,
                 description: "Middle dot".to_string(),
             },
         ]
     }
 
-    pub fn open(&mut self, current_separator: &str) {
+    pub fn open(&mut self, style: &StyleConfig) {
         self.is_open = true;
-        self.input = current_separator.to_string();
+        self.input = style.separator.clone();
+        self.start_cap = style.start_cap.clone();
+        self.end_cap = style.end_cap.clone();
         self.selected_preset = None;
 
         // Check if current separator matches a preset
         for (i, preset) in self.presets.iter().enumerate() {
-            if preset.value == current_separator {
+            if preset.value == style.separator
+                && preset.start_cap == style.start_cap
+                && preset.end_cap == style.end_cap
+            {
                 self.selected_preset = Some(i);
                 break;
             }
@@ -83,6 +140,8 @@ impl SeparatorEditorComponent {
     pub fn close(&mut self) {
         self.is_open = false;
         self.input.clear();
+        self.start_cap = None;
+        self.end_cap = None;
         self.selected_preset = None;
     }
 
@@ -90,13 +149,20 @@ impl SeparatorEditorComponent {
         // Allow most characters for separator
         if !c.is_control() {
             self.input.push(c);
-            self.selected_preset = None; // Clear preset selection when manually editing
+            self.clear_preset();
         }
     }
 
     pub fn backspace(&mut self) {
         self.input.pop();
-        self.selected_preset = None; // Clear preset selection when manually editing
+        self.clear_preset();
+    }
+
+    /// A manually edited separator no longer belongs to a preset, so drop its caps too
+    fn clear_preset(&mut self) {
+        self.selected_preset = None;
+        self.start_cap = None;
+        self.end_cap = None;
     }
 
     pub fn move_preset_selection(&mut self, delta: i32) {
@@ -111,7 +177,10 @@ impl SeparatorEditorComponent {
 
         self.selected_preset = new_selection;
         if let Some(idx) = new_selection {
-            self.input = self.presets[idx].value.clone();
+            let preset = &self.presets[idx];
+            self.input = preset.value.clone();
+            self.start_cap = preset.start_cap.clone();
+            self.end_cap = preset.end_cap.clone();
         }
     }
 
@@ -119,14 +188,29 @@ impl SeparatorEditorComponent {
         self.input.clone()
     }
 
+    /// Clear the separator and both caps
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.start_cap = None;
+        self.end_cap = None;
+        self.selected_preset = None;
+    }
+
+    /// Write the edited separator and caps into the style
+    pub fn apply_to(&self, style: &mut StyleConfig) {
+        style.separator = self.get_separator();
+        style.start_cap = self.start_cap.clone();
+        style.end_cap = self.end_cap.clone();
+    }
+
     pub fn render(&self, f: &mut Frame, area: Rect) {
         if !self.is_open {
             return;
         }
 
         // Calculate exact size needed
-        let popup_height = 15;
-        let popup_width = 60;
+        let popup_height = 21.min(area.height);
+        let popup_width = 60.min(area.width);
         let popup_area = Rect {
             x: (area.width.saturating_sub(popup_width)) / 2,
             y: (area.height.saturating_sub(popup_height)) / 2,
@@ -152,9 +236,15 @@ impl SeparatorEditorComponent {
             ])
             .split(inner);
 
-        // Current input field
+        // Current input field, with the caps on either side when set
+        let current = format!(
+            "{} > {} < {}",
+            self.start_cap.as_deref().unwrap_or(""),
+            self.input,
+            self.end_cap.as_deref().unwrap_or("")
+        );
         f.render_widget(
-            Paragraph::new(format!("> {} <", self.input))
+            Paragraph::new(current.trim().to_string())
                 .style(Style::default().fg(Color::Yellow))
                 .block(
                     Block::default()
@@ -1,5 +1,5 @@
 use super::segment_list::{FieldSelection, Panel};
-use crate::config::{Config, SegmentId, StyleMode};
+use crate::config::{Config, SegmentId, StyleMode, StyleTarget, TextAttribute};
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
@@ -9,11 +9,14 @@ use ratatui::{
 };
 
 #[derive(Default)]
-pub struct SettingsComponent;
+pub struct SettingsComponent {
+    /// Index into `TextAttribute::ALL` of the style toggle Enter flips
+    pub selected_attribute: usize,
+}
 
 impl SettingsComponent {
     pub fn new() -> Self {
-        Self
+        Self::default()
     }
 
     pub fn render(
@@ -35,6 +38,13 @@ impl SettingsComponent {
                 SegmentId::Session => "Session",
                 SegmentId::OutputStyle => "Output Style",
                 SegmentId::Update => "Update",
+                SegmentId::Commit => "Commit",
+                SegmentId::PullRequest => "Pull Request",
+                SegmentId::Project => "Project",
+                SegmentId::Environment => "Environment",
+                SegmentId::System => "System",
+                SegmentId::Clock => "Clock",
+                SegmentId::Productivity => "Productivity",
             };
             let current_icon = match config.style.mode {
                 StyleMode::Plain => &segment.icon.plain,
@@ -89,6 +99,30 @@ impl SettingsComponent {
                 Some(crate::config::AnsiColor::Rgb { r, g, b }) => Color::Rgb(*r, *g, *b),
                 None => Color::White,
             };
+            let secondary_ratatui_color = match &segment.colors.secondary {
+                Some(crate::config::AnsiColor::Color16 { c16 }) => match c16 {
+                    0 => Color::Black,
+                    1 => Color::Red,
+                    2 => Color::Green,
+                    3 => Color::Yellow,
+                    4 => Color::Blue,
+                    5 => Color::Magenta,
+                    6 => Color::Cyan,
+                    7 => Color::White,
+                    8 => Color::DarkGray,
+                    9 => Color::LightRed,
+                    10 => Color::LightGreen,
+                    11 => Color::LightYellow,
+                    12 => Color::LightBlue,
+                    13 => Color::LightMagenta,
+                    14 => Color::LightCyan,
+                    15 => Color::Gray,
+                    _ => Color::White,
+                },
+                Some(crate::config::AnsiColor::Color256 { c256 }) => Color::Indexed(*c256),
+                Some(crate::config::AnsiColor::Rgb { r, g, b }) => Color::Rgb(*r, *g, *b),
+                None => Color::White,
+            };
             let icon_color_desc = match &segment.colors.icon {
                 Some(crate::config::AnsiColor::Color16 { c16 }) => match c16 {
                     0 => "Black".to_string(),
@@ -141,6 +175,32 @@ impl SettingsComponent {
                 }
                 None => "Default".to_string(),
             };
+            let secondary_color_desc = match &segment.colors.secondary {
+                Some(crate::config::AnsiColor::Color16 { c16 }) => match c16 {
+                    0 => "Black".to_string(),
+                    1 => "Red".to_string(),
+                    2 => "Green".to_string(),
+                    3 => "Yellow".to_string(),
+                    4 => "Blue".to_string(),
+                    5 => "Magenta".to_string(),
+                    6 => "Cyan".to_string(),
+                    7 => "White".to_string(),
+                    8 => "Dark Gray".to_string(),
+                    9 => "Light Red".to_string(),
+                    10 => "Light Green".to_string(),
+                    11 => "Light Yellow".to_string(),
+                    12 => "Light Blue".to_string(),
+                    13 => "Light Magenta".to_string(),
+                    14 => "Light Cyan".to_string(),
+                    15 => "Gray".to_string(),
+                    _ => format!("ANSI {}", c16),
+                },
+                Some(crate::config::AnsiColor::Color256 { c256 }) => format!("256:{}", c256),
+                Some(crate::config::AnsiColor::Rgb { r, g, b }) => {
+                    format!("RGB({},{},{})", r, g, b)
+                }
+                None => "Text".to_string(),
+            };
             let background_ratatui_color = match &segment.colors.background {
                 Some(crate::config::AnsiColor::Color16 { c16 }) => match c16 {
                     0 => Color::Black,
@@ -207,6 +267,28 @@ impl SettingsComponent {
                 spans.extend(content);
                 Line::from(spans)
             };
+            // Attribute toggles, with the one Enter flips highlighted while the field is focused
+            let style_spans = |label: &str, target: StyleTarget, field: FieldSelection| {
+                let is_selected = *selected_panel == Panel::Settings && *selected_field == field;
+                let mut spans = vec![Span::raw(label.to_string())];
+                for (i, attribute) in TextAttribute::ALL.iter().enumerate() {
+                    let marker = if segment.styles.is_set(target, *attribute) {
+                        "[✓]"
+                    } else {
+                        "[ ]"
+                    };
+                    let style = if is_selected && i == self.selected_attribute {
+                        Style::default().fg(Color::Cyan)
+                    } else {
+                        Style::default()
+                    };
+                    spans.push(Span::styled(
+                        format!("{} {} ", attribute.name(), marker),
+                        style,
+                    ));
+                }
+                spans
+            };
             let lines = vec![
                 Line::from(format!("{} Segment", segment_name)),
                 create_field_line(
@@ -240,6 +322,20 @@ impl SettingsComponent {
                         Span::styled("██".to_string(), Style::default().fg(text_ratatui_color)),
                     ],
                 ),
+                create_field_line(
+                    FieldSelection::SecondaryColor,
+                    vec![
+                        Span::raw(format!("├─ Secondary Color: {} ", secondary_color_desc)),
+                        if segment.colors.secondary.is_some() {
+                            Span::styled(
+                                "██".to_string(),
+                                Style::default().fg(secondary_ratatui_color),
+                            )
+                        } else {
+                            Span::styled("--".to_string(), Style::default().fg(Color::DarkGray))
+                        },
+                    ],
+                ),
                 create_field_line(
                     FieldSelection::BackgroundColor,
                     vec![
@@ -254,16 +350,29 @@ impl SettingsComponent {
                         },
                     ],
                 ),
+                create_field_line(
+                    FieldSelection::IconStyle,
+                    style_spans(
+                        "├─ Icon Style: ",
+                        StyleTarget::Icon,
+                        FieldSelection::IconStyle,
+                    ),
+                ),
                 create_field_line(
                     FieldSelection::TextStyle,
-                    vec![Span::raw(format!(
-                        "├─ Text Style: Bold {}",
-                        if segment.styles.text_bold {
-                            "[✓]"
-                        } else {
-                            "[ ]"
-                        }
-                    ))],
+                    style_spans(
+                        "├─ Text Style: ",
+                        StyleTarget::Text,
+                        FieldSelection::TextStyle,
+                    ),
+                ),
+                create_field_line(
+                    FieldSelection::SecondaryStyle,
+                    style_spans(
+                        "├─ Secondary Style: ",
+                        StyleTarget::Secondary,
+                        FieldSelection::SecondaryStyle,
+                    ),
                 ),
                 create_field_line(
                     FieldSelection::Options,
@@ -84,28 +84,25 @@ pub fn run_intro() -> Result<(), Box<dyn std::error::Error>> {
                         }
                     }
                 }
-                KeyCode::Char('s') | KeyCode::Char('S') => {
-                    if intro_app.is_awaiting_config_choice() {
-                        intro_app.handle_config_choice('s');
-                        if intro_app.should_continue() {
-                            break Ok(());
-                        }
+                KeyCode::Char('s') | KeyCode::Char('S')
+                    if intro_app.is_awaiting_config_choice() =>
+                {
+                    intro_app.handle_config_choice('s');
+                    if intro_app.should_continue() {
+                        break Ok(());
                     }
                 }
-                KeyCode::Enter | KeyCode::Right => {
+                KeyCode::Enter | KeyCode::Right
                     if !intro_app.is_showing_overwrite_prompt()
-                        && !intro_app.is_awaiting_config_choice()
-                    {
-                        intro_app.next_step();
-                        if intro_app.should_continue() {
-                            break Ok(());
-                        }
+                        && !intro_app.is_awaiting_config_choice() =>
+                {
+                    intro_app.next_step();
+                    if intro_app.should_continue() {
+                        break Ok(());
                     }
                 }
-                KeyCode::Left => {
-                    if !intro_app.is_showing_overwrite_prompt() {
-                        intro_app.prev_step();
-                    }
+                KeyCode::Left if !intro_app.is_showing_overwrite_prompt() => {
+                    intro_app.prev_step();
                 }
                 _ => {}
             }
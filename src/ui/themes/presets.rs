@@ -1,6 +1,6 @@
 // Theme presets for TUI configuration
 
-use crate::config::{Config, StyleConfig, StyleMode};
+use crate::config::{AnsiColor, Config, RoleColors, StyleConfig, StyleMode, TerminalConfig};
 
 // Import all theme modules
 use super::{
@@ -127,6 +127,16 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: " | ".to_string(),
+                start_cap: None,
+                end_cap: None,
+                separator_color: None,
+                roles: RoleColors {
+                    warning: Some(AnsiColor::Color16 { c16: 11 }),
+                    muted: Some(AnsiColor::Color16 { c16: 8 }),
+                    positive: Some(AnsiColor::Color16 { c16: 2 }),
+                    negative: Some(AnsiColor::Color16 { c16: 1 }),
+                    ..Default::default()
+                },
             },
             segments: vec![
                 theme_cometix::model_segment(),
@@ -136,8 +146,17 @@ impl ThemePresets {
                 theme_cometix::cost_segment(),
                 theme_cometix::session_segment(),
                 theme_cometix::output_style_segment(),
+                theme_cometix::commit_segment(),
+                theme_cometix::pull_request_segment(),
+                theme_cometix::project_segment(),
+                theme_cometix::environment_segment(),
+                theme_cometix::system_segment(),
+                theme_cometix::clock_segment(),
+                theme_cometix::productivity_segment(),
             ],
             theme: "cometix".to_string(),
+            rows: Vec::new(),
+            terminal: TerminalConfig::default(),
         }
     }
 
@@ -146,6 +165,16 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::Plain,
                 separator: " | ".to_string(),
+                start_cap: None,
+                end_cap: None,
+                separator_color: None,
+                roles: RoleColors {
+                    warning: Some(AnsiColor::Color16 { c16: 11 }),
+                    muted: Some(AnsiColor::Color16 { c16: 8 }),
+                    positive: Some(AnsiColor::Color16 { c16: 2 }),
+                    negative: Some(AnsiColor::Color16 { c16: 1 }),
+                    ..Default::default()
+                },
             },
             segments: vec![
                 theme_default::model_segment(),
@@ -155,8 +184,17 @@ impl ThemePresets {
                 theme_default::cost_segment(),
                 theme_default::session_segment(),
                 theme_default::output_style_segment(),
+                theme_default::commit_segment(),
+                theme_default::pull_request_segment(),
+                theme_default::project_segment(),
+                theme_default::environment_segment(),
+                theme_default::system_segment(),
+                theme_default::clock_segment(),
+                theme_default::productivity_segment(),
             ],
             theme: "default".to_string(),
+            rows: Vec::new(),
+            terminal: TerminalConfig::default(),
         }
     }
 
@@ -165,6 +203,16 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::Plain,
                 separator: " │ ".to_string(),
+                start_cap: None,
+                end_cap: None,
+                separator_color: None,
+                roles: RoleColors {
+                    warning: Some(AnsiColor::Color16 { c16: 3 }),
+                    muted: Some(AnsiColor::Color16 { c16: 8 }),
+                    positive: Some(AnsiColor::Color16 { c16: 2 }),
+                    negative: Some(AnsiColor::Color16 { c16: 1 }),
+                    ..Default::default()
+                },
             },
             segments: vec![
                 theme_minimal::model_segment(),
@@ -174,8 +222,17 @@ impl ThemePresets {
                 theme_minimal::cost_segment(),
                 theme_minimal::session_segment(),
                 theme_minimal::output_style_segment(),
+                theme_minimal::commit_segment(),
+                theme_minimal::pull_request_segment(),
+                theme_minimal::project_segment(),
+                theme_minimal::environment_segment(),
+                theme_minimal::system_segment(),
+                theme_minimal::clock_segment(),
+                theme_minimal::productivity_segment(),
             ],
             theme: "minimal".to_string(),
+            rows: Vec::new(),
+            terminal: TerminalConfig::default(),
         }
     }
 
@@ -184,6 +241,16 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: " | ".to_string(),
+                start_cap: None,
+                end_cap: None,
+                separator_color: None,
+                roles: RoleColors {
+                    warning: Some(AnsiColor::Color256 { c256: 167 }),
+                    muted: Some(AnsiColor::Color256 { c256: 245 }),
+                    positive: Some(AnsiColor::Color256 { c256: 142 }),
+                    negative: Some(AnsiColor::Color256 { c256: 167 }),
+                    ..Default::default()
+                },
             },
             segments: vec![
                 theme_gruvbox::model_segment(),
@@ -193,8 +260,17 @@ impl ThemePresets {
                 theme_gruvbox::cost_segment(),
                 theme_gruvbox::session_segment(),
                 theme_gruvbox::output_style_segment(),
+                theme_gruvbox::commit_segment(),
+                theme_gruvbox::pull_request_segment(),
+                theme_gruvbox::project_segment(),
+                theme_gruvbox::environment_segment(),
+                theme_gruvbox::system_segment(),
+                theme_gruvbox::clock_segment(),
+                theme_gruvbox::productivity_segment(),
             ],
             theme: "gruvbox".to_string(),
+            rows: Vec::new(),
+            terminal: TerminalConfig::default(),
         }
     }
 
@@ -203,6 +279,10 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                start_cap: None,
+                end_cap: None,
+                separator_color: None,
+                roles: RoleColors::default(),
             },
             segments: vec![
                 theme_nord::model_segment(),
@@ -212,8 +292,17 @@ impl ThemePresets {
                 theme_nord::cost_segment(),
                 theme_nord::session_segment(),
                 theme_nord::output_style_segment(),
+                theme_nord::commit_segment(),
+                theme_nord::pull_request_segment(),
+                theme_nord::project_segment(),
+                theme_nord::environment_segment(),
+                theme_nord::system_segment(),
+                theme_nord::clock_segment(),
+                theme_nord::productivity_segment(),
             ],
             theme: "nord".to_string(),
+            rows: Vec::new(),
+            terminal: TerminalConfig::default(),
         }
     }
 
@@ -222,6 +311,10 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                start_cap: None,
+                end_cap: None,
+                separator_color: None,
+                roles: RoleColors::default(),
             },
             segments: vec![
                 theme_powerline_dark::model_segment(),
@@ -231,8 +324,17 @@ impl ThemePresets {
                 theme_powerline_dark::cost_segment(),
                 theme_powerline_dark::session_segment(),
                 theme_powerline_dark::output_style_segment(),
+                theme_powerline_dark::commit_segment(),
+                theme_powerline_dark::pull_request_segment(),
+                theme_powerline_dark::project_segment(),
+                theme_powerline_dark::environment_segment(),
+                theme_powerline_dark::system_segment(),
+                theme_powerline_dark::clock_segment(),
+                theme_powerline_dark::productivity_segment(),
             ],
             theme: "powerline-dark".to_string(),
+            rows: Vec::new(),
+            terminal: TerminalConfig::default(),
         }
     }
 
@@ -241,6 +343,10 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                start_cap: None,
+                end_cap: None,
+                separator_color: None,
+                roles: RoleColors::default(),
             },
             segments: vec![
                 theme_powerline_light::model_segment(),
@@ -250,8 +356,17 @@ impl ThemePresets {
                 theme_powerline_light::cost_segment(),
                 theme_powerline_light::session_segment(),
                 theme_powerline_light::output_style_segment(),
+                theme_powerline_light::commit_segment(),
+                theme_powerline_light::pull_request_segment(),
+                theme_powerline_light::project_segment(),
+                theme_powerline_light::environment_segment(),
+                theme_powerline_light::system_segment(),
+                theme_powerline_light::clock_segment(),
+                theme_powerline_light::productivity_segment(),
             ],
             theme: "powerline-light".to_string(),
+            rows: Vec::new(),
+            terminal: TerminalConfig::default(),
         }
     }
 
@@ -260,6 +375,10 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                start_cap: None,
+                end_cap: None,
+                separator_color: None,
+                roles: RoleColors::default(),
             },
             segments: vec![
                 theme_powerline_rose_pine::model_segment(),
@@ -269,8 +388,17 @@ impl ThemePresets {
                 theme_powerline_rose_pine::cost_segment(),
                 theme_powerline_rose_pine::session_segment(),
                 theme_powerline_rose_pine::output_style_segment(),
+                theme_powerline_rose_pine::commit_segment(),
+                theme_powerline_rose_pine::pull_request_segment(),
+                theme_powerline_rose_pine::project_segment(),
+                theme_powerline_rose_pine::environment_segment(),
+                theme_powerline_rose_pine::system_segment(),
+                theme_powerline_rose_pine::clock_segment(),
+                theme_powerline_rose_pine::productivity_segment(),
             ],
             theme: "powerline-rose-pine".to_string(),
+            rows: Vec::new(),
+            terminal: TerminalConfig::default(),
         }
     }
 
@@ -279,6 +407,10 @@ impl ThemePresets {
             style: StyleConfig {
                 mode: StyleMode::NerdFont,
                 separator: "".to_string(),
+                start_cap: None,
+                end_cap: None,
+                separator_color: None,
+                roles: RoleColors::default(),
             },
             segments: vec![
                 theme_powerline_tokyo_night::model_segment(),
@@ -288,8 +420,17 @@ impl ThemePresets {
                 theme_powerline_tokyo_night::cost_segment(),
                 theme_powerline_tokyo_night::session_segment(),
                 theme_powerline_tokyo_night::output_style_segment(),
+                theme_powerline_tokyo_night::commit_segment(),
+                theme_powerline_tokyo_night::pull_request_segment(),
+                theme_powerline_tokyo_night::project_segment(),
+                theme_powerline_tokyo_night::environment_segment(),
+                theme_powerline_tokyo_night::system_segment(),
+                theme_powerline_tokyo_night::clock_segment(),
+                theme_powerline_tokyo_night::productivity_segment(),
             ],
             theme: "powerline-tokyo-night".to_string(),
+            rows: Vec::new(),
+            terminal: TerminalConfig::default(),
         }
     }
 }
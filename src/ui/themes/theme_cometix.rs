@@ -1,5 +1,5 @@
 use crate::config::{
-    AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, TextStyleConfig,
+    AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, SegmentLayout, TextStyleConfig,
 };
 use std::collections::HashMap;
 
@@ -15,8 +15,13 @@ pub fn model_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 14 }),
             text: Some(AnsiColor::Color16 { c16: 14 }),
             background: None,
+            secondary: None,
         },
-        styles: TextStyleConfig { text_bold: true },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        layout: SegmentLayout::default(),
         options: HashMap::new(),
     }
 }
@@ -33,8 +38,13 @@ pub fn directory_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 11 }),
             text: Some(AnsiColor::Color16 { c16: 10 }),
             background: None,
+            secondary: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
+        layout: SegmentLayout::default(),
         options: HashMap::new(),
     }
 }
@@ -51,8 +61,13 @@ pub fn git_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 12 }),
             text: Some(AnsiColor::Color16 { c16: 12 }),
             background: None,
+            secondary: None,
         },
-        styles: TextStyleConfig { text_bold: true },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        layout: SegmentLayout::default(),
         options: {
             let mut opts = HashMap::new();
             opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
@@ -73,8 +88,13 @@ pub fn usage_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 13 }),
             text: Some(AnsiColor::Color16 { c16: 13 }),
             background: None,
+            secondary: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
+        layout: SegmentLayout::default(),
         options: HashMap::new(),
     }
 }
@@ -91,8 +111,13 @@ pub fn cost_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 3 }),
             text: Some(AnsiColor::Color16 { c16: 3 }),
             background: None,
+            secondary: None,
         },
-        styles: TextStyleConfig { text_bold: true },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        layout: SegmentLayout::default(),
         options: HashMap::new(),
     }
 }
@@ -109,8 +134,13 @@ pub fn session_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 2 }),
             text: Some(AnsiColor::Color16 { c16: 2 }),
             background: None,
+            secondary: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
         },
-        styles: TextStyleConfig { text_bold: true },
+        layout: SegmentLayout::default(),
         options: HashMap::new(),
     }
 }
@@ -127,8 +157,224 @@ pub fn output_style_segment() -> SegmentConfig {
             icon: Some(AnsiColor::Color16 { c16: 6 }),
             text: Some(AnsiColor::Color16 { c16: 6 }),
             background: None,
+            secondary: None,
         },
-        styles: TextStyleConfig { text_bold: true },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        layout: SegmentLayout::default(),
         options: HashMap::new(),
     }
 }
+
+pub fn commit_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Commit,
+        enabled: false,
+        icon: IconConfig {
+            plain: "📝".to_string(),
+            nerd_font: "\u{f417}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Color16 { c16: 2 }),
+            text: Some(AnsiColor::Color16 { c16: 2 }),
+            background: None,
+            secondary: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_author".to_string(), serde_json::Value::Bool(false));
+            opts.insert(
+                "show_session_commits".to_string(),
+                serde_json::Value::Bool(true),
+            );
+            opts
+        },
+    }
+}
+
+pub fn pull_request_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::PullRequest,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🔀".to_string(),
+            nerd_font: "\u{f407}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Color16 { c16: 12 }),
+            text: Some(AnsiColor::Color16 { c16: 12 }),
+            background: None,
+            secondary: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_forge".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+    }
+}
+
+pub fn project_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Project,
+        enabled: false,
+        icon: IconConfig {
+            plain: "📦".to_string(),
+            nerd_font: "\u{f121}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Color16 { c16: 6 }),
+            text: Some(AnsiColor::Color16 { c16: 6 }),
+            background: None,
+            secondary: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_version".to_string(), serde_json::Value::Bool(true));
+            opts.insert("type_icons".to_string(), serde_json::Value::Bool(true));
+            opts
+        },
+    }
+}
+
+pub fn environment_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Environment,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🌐".to_string(),
+            nerd_font: "\u{f0ac}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Color16 { c16: 2 }),
+            text: Some(AnsiColor::Color16 { c16: 2 }),
+            background: None,
+            secondary: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_venv".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_conda".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_container".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_ssh".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_nix".to_string(), serde_json::Value::Bool(true));
+            opts
+        },
+    }
+}
+
+pub fn system_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::System,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🖥️".to_string(),
+            nerd_font: "\u{f4bc}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Color16 { c16: 13 }),
+            text: Some(AnsiColor::Color16 { c16: 13 }),
+            background: None,
+            secondary: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_load".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_memory".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_disk".to_string(), serde_json::Value::Bool(true));
+            opts
+        },
+    }
+}
+
+pub fn clock_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Clock,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🕐".to_string(),
+            nerd_font: "\u{f017}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Color16 { c16: 3 }),
+            text: Some(AnsiColor::Color16 { c16: 3 }),
+            background: None,
+            secondary: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "format".to_string(),
+                serde_json::Value::String("%H:%M".to_string()),
+            );
+            opts.insert("show_idle".to_string(), serde_json::Value::Bool(true));
+            opts.insert(
+                "idle_threshold_secs".to_string(),
+                serde_json::Value::Number(60.into()),
+            );
+            opts
+        },
+    }
+}
+
+pub fn productivity_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Productivity,
+        enabled: false,
+        icon: IconConfig {
+            plain: "📈".to_string(),
+            nerd_font: "\u{f201}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Color16 { c16: 3 }),
+            text: Some(AnsiColor::Color16 { c16: 3 }),
+            background: None,
+            secondary: None,
+        },
+        styles: TextStyleConfig {
+            text_bold: true,
+            ..Default::default()
+        },
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "metrics".to_string(),
+                serde_json::json!(["net", "rate", "cost"]),
+            );
+            opts
+        },
+    }
+}
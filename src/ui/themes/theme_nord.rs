@@ -1,5 +1,5 @@
 use crate::config::{
-    AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, TextStyleConfig,
+    AnsiColor, ColorConfig, IconConfig, SegmentConfig, SegmentId, SegmentLayout, TextStyleConfig,
 };
 use std::collections::HashMap;
 
@@ -27,8 +27,10 @@ pub fn model_segment() -> SegmentConfig {
                 g: 192,
                 b: 208,
             }),
+            secondary: None,
         },
         styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
         options: HashMap::new(),
     }
 }
@@ -57,8 +59,10 @@ pub fn directory_segment() -> SegmentConfig {
                 g: 190,
                 b: 140,
             }),
+            secondary: None,
         },
         styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
         options: HashMap::new(),
     }
 }
@@ -87,8 +91,10 @@ pub fn git_segment() -> SegmentConfig {
                 g: 161,
                 b: 193,
             }),
+            secondary: None,
         },
         styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
         options: {
             let mut opts = HashMap::new();
             opts.insert("show_sha".to_string(), serde_json::Value::Bool(false));
@@ -121,8 +127,10 @@ pub fn usage_segment() -> SegmentConfig {
                 g: 142,
                 b: 173,
             }),
+            secondary: None,
         },
         styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
         options: HashMap::new(),
     }
 }
@@ -151,8 +159,10 @@ pub fn cost_segment() -> SegmentConfig {
                 g: 203,
                 b: 139,
             }), // Nord yellow background
+            secondary: None,
         },
         styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
         options: HashMap::new(),
     }
 }
@@ -181,8 +191,10 @@ pub fn session_segment() -> SegmentConfig {
                 g: 190,
                 b: 140,
             }), // Nord green background
+            secondary: None,
         },
         styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
         options: HashMap::new(),
     }
 }
@@ -211,8 +223,284 @@ pub fn output_style_segment() -> SegmentConfig {
                 g: 192,
                 b: 208,
             }), // Nord cyan background
+            secondary: None,
         },
         styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
         options: HashMap::new(),
     }
 }
+
+pub fn commit_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Commit,
+        enabled: false,
+        icon: IconConfig {
+            plain: "📝".to_string(),
+            nerd_font: "\u{f417}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+            secondary: None,
+        },
+        styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_author".to_string(), serde_json::Value::Bool(false));
+            opts.insert(
+                "show_session_commits".to_string(),
+                serde_json::Value::Bool(true),
+            );
+            opts
+        },
+    }
+}
+
+pub fn pull_request_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::PullRequest,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🔀".to_string(),
+            nerd_font: "\u{f407}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 129,
+                g: 161,
+                b: 193,
+            }),
+            secondary: None,
+        },
+        styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_forge".to_string(), serde_json::Value::Bool(false));
+            opts
+        },
+    }
+}
+
+pub fn project_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Project,
+        enabled: false,
+        icon: IconConfig {
+            plain: "📦".to_string(),
+            nerd_font: "\u{f121}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 136,
+                g: 192,
+                b: 208,
+            }), // Nord cyan background
+            secondary: None,
+        },
+        styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_version".to_string(), serde_json::Value::Bool(true));
+            opts.insert("type_icons".to_string(), serde_json::Value::Bool(true));
+            opts
+        },
+    }
+}
+
+pub fn environment_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Environment,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🌐".to_string(),
+            nerd_font: "\u{f0ac}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 163,
+                g: 190,
+                b: 140,
+            }), // Nord green background
+            secondary: None,
+        },
+        styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_venv".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_conda".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_container".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_ssh".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_nix".to_string(), serde_json::Value::Bool(true));
+            opts
+        },
+    }
+}
+
+pub fn system_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::System,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🖥️".to_string(),
+            nerd_font: "\u{f4bc}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 180,
+                g: 142,
+                b: 173,
+            }),
+            secondary: None,
+        },
+        styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert("show_load".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_memory".to_string(), serde_json::Value::Bool(true));
+            opts.insert("show_disk".to_string(), serde_json::Value::Bool(true));
+            opts
+        },
+    }
+}
+
+pub fn clock_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Clock,
+        enabled: false,
+        icon: IconConfig {
+            plain: "🕐".to_string(),
+            nerd_font: "\u{f017}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 235,
+                g: 203,
+                b: 139,
+            }), // Nord yellow background
+            secondary: None,
+        },
+        styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "format".to_string(),
+                serde_json::Value::String("%H:%M".to_string()),
+            );
+            opts.insert("show_idle".to_string(), serde_json::Value::Bool(true));
+            opts.insert(
+                "idle_threshold_secs".to_string(),
+                serde_json::Value::Number(60.into()),
+            );
+            opts
+        },
+    }
+}
+
+pub fn productivity_segment() -> SegmentConfig {
+    SegmentConfig {
+        id: SegmentId::Productivity,
+        enabled: false,
+        icon: IconConfig {
+            plain: "📈".to_string(),
+            nerd_font: "\u{f201}".to_string(),
+        },
+        colors: ColorConfig {
+            icon: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            text: Some(AnsiColor::Rgb {
+                r: 46,
+                g: 52,
+                b: 64,
+            }),
+            background: Some(AnsiColor::Rgb {
+                r: 235,
+                g: 203,
+                b: 139,
+            }), // Nord yellow background
+            secondary: None,
+        },
+        styles: TextStyleConfig::default(),
+        layout: SegmentLayout::default(),
+        options: {
+            let mut opts = HashMap::new();
+            opts.insert(
+                "metrics".to_string(),
+                serde_json::json!(["net", "rate", "cost"]),
+            );
+            opts
+        },
+    }
+}
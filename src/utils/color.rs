@@ -0,0 +1,313 @@
+//! Terminal color capability detection and color downsampling
+
+use crate::config::{AnsiColor, ColorDepth};
+use std::env;
+
+/// xterm's default values for the 16 standard colors
+const ANSI16_PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Channel levels of the 6x6x6 color cube at indices 16-231
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Color depth from the environment: `NO_COLOR`, then `COLORTERM`, `TERM_PROGRAM`
+/// and `TERM`. Without any hint truecolor is assumed, as before detection existed.
+pub fn detect_color_depth() -> ColorDepth {
+    detect_color_depth_from(|name| env::var(name).ok())
+}
+
+/// Color depth from variables looked up through `lookup`; empty values count as unset
+pub fn detect_color_depth_from(lookup: impl Fn(&str) -> Option<String>) -> ColorDepth {
+    let var = |name: &str| lookup(name).filter(|value| !value.is_empty());
+
+    if var("NO_COLOR").is_some() {
+        return ColorDepth::None;
+    }
+
+    if let Some(colorterm) = var("COLORTERM") {
+        if matches!(colorterm.as_str(), "truecolor" | "24bit") {
+            return ColorDepth::TrueColor;
+        }
+    }
+
+    // Terminals known to support truecolor even when TERM says otherwise
+    if let Some(program) = var("TERM_PROGRAM") {
+        if matches!(
+            program.as_str(),
+            "iTerm.app" | "WezTerm" | "vscode" | "ghostty"
+        ) {
+            return ColorDepth::TrueColor;
+        }
+    }
+
+    match var("TERM") {
+        Some(term) if term == "dumb" => ColorDepth::None,
+        Some(term) if term.contains("truecolor") || term.contains("direct") => {
+            ColorDepth::TrueColor
+        }
+        Some(term) if term.contains("256") => ColorDepth::Ansi256,
+        Some(_) => ColorDepth::Ansi16,
+        None => ColorDepth::TrueColor,
+    }
+}
+
+/// Convert a color to the nearest one the terminal can display
+pub fn downsample(color: &AnsiColor, depth: ColorDepth) -> AnsiColor {
+    match (color, depth) {
+        (AnsiColor::Rgb { r, g, b }, ColorDepth::Ansi256) => AnsiColor::Color256 {
+            c256: nearest_256((*r, *g, *b)),
+        },
+        (AnsiColor::Rgb { r, g, b }, ColorDepth::Ansi16 | ColorDepth::None) => AnsiColor::Color16 {
+            c16: nearest_16((*r, *g, *b)),
+        },
+        (AnsiColor::Color256 { c256 }, ColorDepth::Ansi16 | ColorDepth::None) if *c256 >= 16 => {
+            AnsiColor::Color16 {
+                c16: nearest_16(ansi256_to_rgb(*c256)),
+            }
+        }
+        (AnsiColor::Color256 { c256 }, ColorDepth::Ansi16 | ColorDepth::None) => {
+            AnsiColor::Color16 { c16: *c256 }
+        }
+        _ => color.clone(),
+    }
+}
+
+/// RGB value a color is displayed as, using xterm's palette for indexed colors
+pub fn to_rgb(color: &AnsiColor) -> (u8, u8, u8) {
+    match color {
+        AnsiColor::Color16 { c16 } => ANSI16_PALETTE[(*c16 as usize).min(15)],
+        AnsiColor::Color256 { c256 } => ansi256_to_rgb(*c256),
+        AnsiColor::Rgb { r, g, b } => (*r, *g, *b),
+    }
+}
+
+/// `#rrggbb` form of a color, for formats outside the terminal
+pub fn to_hex(color: &AnsiColor) -> String {
+    let (r, g, b) = to_rgb(color);
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// RGB value of a 256-color palette index
+fn ansi256_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16_PALETTE[index as usize],
+        16..=231 => {
+            let cube = index - 16;
+            (
+                CUBE_LEVELS[(cube / 36) as usize],
+                CUBE_LEVELS[((cube / 6) % 6) as usize],
+                CUBE_LEVELS[(cube % 6) as usize],
+            )
+        }
+        232..=255 => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+/// Nearest index in the color cube and grayscale ramp. The first 16 colors are
+/// skipped because terminal themes redefine them.
+fn nearest_256(rgb: (u8, u8, u8)) -> u8 {
+    (16..=255)
+        .min_by_key(|&index| distance(rgb, ansi256_to_rgb(index)))
+        .unwrap_or(16)
+}
+
+fn nearest_16(rgb: (u8, u8, u8)) -> u8 {
+    (0..16)
+        .min_by_key(|&index| distance(rgb, ANSI16_PALETTE[index as usize]))
+        .unwrap_or(0)
+}
+
+/// "Redmean" weighted distance, a cheap approximation of perceived color difference
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let mean_r = (a.0 as i32 + b.0 as i32) / 2;
+    let dr = a.0 as i32 - b.0 as i32;
+    let dg = a.1 as i32 - b.1 as i32;
+    let db = a.2 as i32 - b.2 as i32;
+    (((512 + mean_r) * dr * dr) / 256 + 4 * dg * dg + ((767 - mean_r) * db * db) / 256) as u32
+}
+
+/// Remove ANSI escape sequences, leaving the visible text
+pub fn strip_ansi(text: &str) -> String {
+    let mut visible = String::new();
+    let mut in_escape = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            // Start of ANSI escape sequence
+            in_escape = true;
+            // Skip the [ character
+            if chars.peek() == Some(&'[') {
+                chars.next();
+            }
+        } else if in_escape {
+            // Skip until we find the end of the escape sequence (letter)
+            if ch.is_alphabetic() {
+                in_escape = false;
+            }
+        } else {
+            // Regular character
+            visible.push(ch);
+        }
+    }
+
+    visible
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Run detection with only the given variables set
+    fn detect_with(vars: &[(&str, &str)]) -> ColorDepth {
+        let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+        detect_color_depth_from(|name| vars.get(name).map(|value| value.to_string()))
+    }
+
+    #[test]
+    fn detect_color_depth_from_reads_variables() {
+        assert_eq!(detect_with(&[]), ColorDepth::TrueColor);
+        assert_eq!(
+            detect_with(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")]),
+            ColorDepth::None
+        );
+        // An empty NO_COLOR does not disable colors
+        assert_eq!(
+            detect_with(&[("NO_COLOR", ""), ("TERM", "xterm-256color")]),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(
+            detect_with(&[("COLORTERM", "24bit"), ("TERM", "xterm")]),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            detect_with(&[("TERM_PROGRAM", "WezTerm"), ("TERM", "xterm")]),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            detect_with(&[
+                ("TERM_PROGRAM", "Apple_Terminal"),
+                ("TERM", "xterm-256color")
+            ]),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(
+            detect_with(&[("TERM", "xterm-direct")]),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(detect_with(&[("TERM", "xterm")]), ColorDepth::Ansi16);
+        assert_eq!(detect_with(&[("TERM", "dumb")]), ColorDepth::None);
+    }
+
+    #[test]
+    fn downsample_keeps_supported_colors() {
+        let rgb = AnsiColor::Rgb { r: 1, g: 2, b: 3 };
+        assert_eq!(downsample(&rgb, ColorDepth::TrueColor), rgb);
+        let c256 = AnsiColor::Color256 { c256: 208 };
+        assert_eq!(downsample(&c256, ColorDepth::Ansi256), c256);
+        let c16 = AnsiColor::Color16 { c16: 12 };
+        assert_eq!(downsample(&c16, ColorDepth::Ansi16), c16);
+    }
+
+    #[test]
+    fn downsample_rgb_to_256_uses_cube_and_grayscale() {
+        let orange = AnsiColor::Rgb {
+            r: 255,
+            g: 135,
+            b: 0,
+        };
+        assert_eq!(
+            downsample(&orange, ColorDepth::Ansi256),
+            AnsiColor::Color256 { c256: 208 }
+        );
+        let gray = AnsiColor::Rgb {
+            r: 128,
+            g: 128,
+            b: 128,
+        };
+        assert_eq!(
+            downsample(&gray, ColorDepth::Ansi256),
+            AnsiColor::Color256 { c256: 244 }
+        );
+        // Never picks the theme-dependent first 16 colors
+        let black = AnsiColor::Rgb { r: 0, g: 0, b: 0 };
+        assert_eq!(
+            downsample(&black, ColorDepth::Ansi256),
+            AnsiColor::Color256 { c256: 16 }
+        );
+    }
+
+    #[test]
+    fn downsample_to_16_colors() {
+        let red = AnsiColor::Rgb {
+            r: 250,
+            g: 10,
+            b: 10,
+        };
+        assert_eq!(
+            downsample(&red, ColorDepth::Ansi16),
+            AnsiColor::Color16 { c16: 9 }
+        );
+        let magenta = AnsiColor::Color256 { c256: 200 };
+        assert_eq!(
+            downsample(&magenta, ColorDepth::Ansi16),
+            AnsiColor::Color16 { c16: 13 }
+        );
+        // Low palette indices are the 16 colors themselves
+        let yellow = AnsiColor::Color256 { c256: 3 };
+        assert_eq!(
+            downsample(&yellow, ColorDepth::None),
+            AnsiColor::Color16 { c16: 3 }
+        );
+    }
+
+    #[test]
+    fn ansi256_to_rgb_covers_palette_ranges() {
+        assert_eq!(ansi256_to_rgb(1), (205, 0, 0));
+        assert_eq!(ansi256_to_rgb(16), (0, 0, 0));
+        assert_eq!(ansi256_to_rgb(200), (255, 0, 215));
+        assert_eq!(ansi256_to_rgb(231), (255, 255, 255));
+        assert_eq!(ansi256_to_rgb(232), (8, 8, 8));
+        assert_eq!(ansi256_to_rgb(255), (238, 238, 238));
+    }
+
+    #[test]
+    fn to_hex_formats_every_color_kind() {
+        assert_eq!(to_hex(&AnsiColor::Color16 { c16: 1 }), "#cd0000");
+        assert_eq!(to_hex(&AnsiColor::Color256 { c256: 244 }), "#808080");
+        assert_eq!(
+            to_hex(&AnsiColor::Rgb {
+                r: 1,
+                g: 171,
+                b: 255
+            }),
+            "#01abff"
+        );
+    }
+
+    #[test]
+    fn strip_ansi_keeps_visible_text() {
+        assert_eq!(strip_ansi("\x1b[1;31mred\x1b[0m plain"), "red plain");
+        assert_eq!(strip_ansi("\x1b[4:3m\x1b[58:5:208mcurly\x1b[0m"), "curly");
+    }
+}
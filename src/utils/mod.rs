@@ -1,3 +1,6 @@
 pub mod claude_code_patcher;
+pub mod color;
+pub mod transcript;
+pub mod width;
 
 pub use claude_code_patcher::{ClaudeCodePatcher, LocationResult};
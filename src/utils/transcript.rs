@@ -0,0 +1,77 @@
+//! Shared helpers for reading Claude Code transcript files
+
+use crate::config::TranscriptEntry;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Iterate over parsed transcript entries, skipping blank or malformed lines
+pub fn entries<P: AsRef<Path>>(path: P) -> impl Iterator<Item = TranscriptEntry> {
+    fs::File::open(path)
+        .ok()
+        .map(|file| BufReader::new(file).lines())
+        .into_iter()
+        .flatten()
+        .map_while(Result::ok)
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<TranscriptEntry>(&line).ok())
+}
+
+/// Parse an RFC 3339 transcript timestamp
+pub fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Time of the first timestamped entry, i.e. when the session started
+pub fn session_start<P: AsRef<Path>>(path: P) -> Option<DateTime<Utc>> {
+    entries(path).find_map(|entry| entry.timestamp.as_deref().and_then(parse_timestamp))
+}
+
+/// Time of the most recent assistant message, i.e. when Claude last responded
+pub fn last_assistant_message<P: AsRef<Path>>(path: P) -> Option<DateTime<Utc>> {
+    entries(path)
+        .filter(|entry| entry.r#type.as_deref() == Some("assistant"))
+        .filter_map(|entry| entry.timestamp.as_deref().and_then(parse_timestamp))
+        .last()
+}
+
+/// Number of model requests in the session. Responses split into several
+/// entries share a message id and are counted once.
+pub fn api_turns<P: AsRef<Path>>(path: P) -> usize {
+    let mut seen = HashSet::new();
+    entries(path)
+        .filter(|entry| entry.r#type.as_deref() == Some("assistant"))
+        .filter(
+            |entry| match entry.message.as_ref().and_then(|m| m.id.clone()) {
+                Some(id) => seen.insert(id),
+                None => true,
+            },
+        )
+        .count()
+}
+
+/// Entries that make up the most recent assistant response
+pub fn last_assistant_response<P: AsRef<Path>>(path: P) -> Vec<TranscriptEntry> {
+    let mut response: Vec<TranscriptEntry> = Vec::new();
+    for entry in entries(path) {
+        if entry.r#type.as_deref() != Some("assistant") {
+            continue;
+        }
+        let id = entry.message.as_ref().and_then(|m| m.id.as_deref());
+        let same_response = id.is_some()
+            && response
+                .last()
+                .and_then(|last| last.message.as_ref())
+                .and_then(|m| m.id.as_deref())
+                == id;
+        if !same_response {
+            response.clear();
+        }
+        response.push(entry);
+    }
+    response
+}
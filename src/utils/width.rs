@@ -0,0 +1,232 @@
+//! Display width of statusline text in terminal columns
+
+use crate::config::TerminalConfig;
+use crate::utils::color::strip_ansi;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Private-use code points, where Nerd Font glyphs live
+fn is_private_use(ch: char) -> bool {
+    matches!(
+        ch,
+        '\u{e000}'..='\u{f8ff}' | '\u{f0000}'..='\u{ffffd}' | '\u{100000}'..='\u{10fffd}'
+    )
+}
+
+/// Columns a single character occupies. Wide CJK and emoji take two columns;
+/// East Asian ambiguous characters and private-use glyphs take one unless the
+/// terminal config says otherwise.
+pub fn char_width(ch: char, terminal: &TerminalConfig) -> usize {
+    if is_private_use(ch) {
+        return if terminal.private_use_wide { 2 } else { 1 };
+    }
+
+    let width = if terminal.ambiguous_wide {
+        ch.width_cjk()
+    } else {
+        ch.width()
+    };
+    width.unwrap_or(0)
+}
+
+/// Columns a grapheme cluster occupies. Emoji sequences joined with ZWJ or
+/// turned into emoji with VS16 (`🖥️`, `👨‍💻`) are drawn as one wide glyph.
+fn cluster_width(cluster: &str, terminal: &TerminalConfig) -> usize {
+    match cluster.chars().next() {
+        Some(ch) if is_private_use(ch) => char_width(ch, terminal),
+        Some(_) if terminal.ambiguous_wide => cluster.width_cjk(),
+        Some(_) => cluster.width(),
+        None => 0,
+    }
+}
+
+/// Columns occupied by plain text
+pub fn str_width(text: &str, terminal: &TerminalConfig) -> usize {
+    text.graphemes(true)
+        .map(|cluster| cluster_width(cluster, terminal))
+        .sum()
+}
+
+/// Strip ANSI escape sequences and return the columns the remaining text occupies
+pub fn visible_width(text: &str, terminal: &TerminalConfig) -> usize {
+    str_width(&strip_ansi(text), terminal)
+}
+
+/// Cut plain text to at most `max_width` columns, keeping the start and
+/// marking the cut with an ellipsis. Grapheme clusters are never split.
+pub fn truncate_end(text: &str, max_width: usize, terminal: &TerminalConfig) -> String {
+    if str_width(text, terminal) <= max_width {
+        return text.to_string();
+    }
+
+    let budget = max_width.saturating_sub(char_width('…', terminal));
+    let mut truncated = String::new();
+    let mut width = 0;
+    for cluster in text.graphemes(true) {
+        let cluster_width = cluster_width(cluster, terminal);
+        if width + cluster_width > budget {
+            break;
+        }
+        truncated.push_str(cluster);
+        width += cluster_width;
+    }
+    truncated.push('…');
+    truncated
+}
+
+/// Cut plain text to at most `max_width` columns, keeping the end and
+/// marking the cut with a leading ellipsis. Grapheme clusters are never split.
+pub fn truncate_start(text: &str, max_width: usize, terminal: &TerminalConfig) -> String {
+    if str_width(text, terminal) <= max_width {
+        return text.to_string();
+    }
+
+    let budget = max_width.saturating_sub(char_width('…', terminal));
+    let mut tail = Vec::new();
+    let mut width = 0;
+    for cluster in text.graphemes(true).rev() {
+        let cluster_width = cluster_width(cluster, terminal);
+        if width + cluster_width > budget {
+            break;
+        }
+        tail.push(cluster);
+        width += cluster_width;
+    }
+    std::iter::once("…").chain(tail.into_iter().rev()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn narrow() -> TerminalConfig {
+        TerminalConfig::default()
+    }
+
+    fn ambiguous_wide() -> TerminalConfig {
+        TerminalConfig {
+            ambiguous_wide: true,
+            ..Default::default()
+        }
+    }
+
+    fn private_use_wide() -> TerminalConfig {
+        TerminalConfig {
+            private_use_wide: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn char_width_counts_wide_and_zero_width_characters() {
+        assert_eq!(char_width('a', &narrow()), 1);
+        assert_eq!(char_width('中', &narrow()), 2);
+        assert_eq!(char_width('🤖', &narrow()), 2);
+        assert_eq!(char_width('\u{301}', &narrow()), 0);
+    }
+
+    #[test]
+    fn char_width_follows_ambiguous_setting() {
+        assert_eq!(char_width('·', &narrow()), 1);
+        assert_eq!(char_width('·', &ambiguous_wide()), 2);
+        assert_eq!(char_width('…', &ambiguous_wide()), 2);
+    }
+
+    #[test]
+    fn char_width_follows_private_use_setting() {
+        assert_eq!(char_width('\u{e0a0}', &narrow()), 1);
+        assert_eq!(char_width('\u{e0a0}', &private_use_wide()), 2);
+        assert_eq!(char_width('\u{f0001}', &private_use_wide()), 2);
+        // Private-use glyphs are not ambiguous characters
+        assert_eq!(char_width('\u{e0a0}', &ambiguous_wide()), 1);
+    }
+
+    #[test]
+    fn str_width_sums_characters() {
+        assert_eq!(str_width("", &narrow()), 0);
+        assert_eq!(str_width("ab中", &narrow()), 4);
+        assert_eq!(str_width("a · b", &ambiguous_wide()), 6);
+    }
+
+    #[test]
+    fn str_width_measures_emoji_sequences_as_one_glyph() {
+        // Emoji presentation selector (VS16)
+        assert_eq!(str_width("🖥\u{fe0f}", &narrow()), 2);
+        assert_eq!(str_width("⚠\u{fe0f}", &narrow()), 2);
+        assert_eq!(str_width("⚠\u{fe0f}", &ambiguous_wide()), 2);
+        // ZWJ sequence
+        assert_eq!(str_width("👨\u{200d}💻", &narrow()), 2);
+        assert_eq!(str_width("a👨\u{200d}💻b", &narrow()), 4);
+    }
+
+    #[test]
+    fn truncation_keeps_emoji_sequences_whole() {
+        let text = "ab👨\u{200d}💻cd";
+        assert_eq!(truncate_end(text, 5, &narrow()), "ab👨\u{200d}💻…");
+        assert_eq!(truncate_end(text, 4, &narrow()), "ab…");
+        assert_eq!(truncate_start(text, 5, &narrow()), "…👨\u{200d}💻cd");
+        assert_eq!(truncate_start(text, 4, &narrow()), "…cd");
+        assert_eq!(truncate_end("🖥\u{fe0f} host", 3, &narrow()), "🖥\u{fe0f}…");
+    }
+
+    #[test]
+    fn visible_width_ignores_escape_sequences() {
+        let text = "\x1b[1;38;5;208mmaster\x1b[0m \x1b[4:3m中\x1b[0m";
+        assert_eq!(visible_width(text, &narrow()), 9);
+        assert_eq!(visible_width("\x1b[0m", &narrow()), 0);
+    }
+
+    #[test]
+    fn truncate_end_keeps_short_text() {
+        assert_eq!(truncate_end("abc", 3, &narrow()), "abc");
+        assert_eq!(truncate_end("", 0, &narrow()), "");
+    }
+
+    #[test]
+    fn truncate_end_marks_the_cut() {
+        assert_eq!(truncate_end("abcdef", 4, &narrow()), "abc…");
+        assert_eq!(truncate_end("abcdef", 1, &narrow()), "…");
+    }
+
+    #[test]
+    fn truncate_end_does_not_split_wide_characters() {
+        // "中" would end at column 3, past the two columns left before the ellipsis
+        assert_eq!(truncate_end("a中文b", 3, &narrow()), "a…");
+        assert_eq!(truncate_end("a中文b", 4, &narrow()), "a中…");
+    }
+
+    #[test]
+    fn truncate_end_counts_wide_ellipsis() {
+        let truncated = truncate_end("abcdef", 4, &ambiguous_wide());
+        assert_eq!(truncated, "ab…");
+        assert_eq!(str_width(&truncated, &ambiguous_wide()), 4);
+    }
+
+    #[test]
+    fn truncate_end_counts_wide_private_use_glyphs() {
+        let text = "\u{e0a0}\u{e0a0}\u{e0a0}";
+        assert_eq!(truncate_end(text, 4, &narrow()), text);
+        assert_eq!(truncate_end(text, 4, &private_use_wide()), "\u{e0a0}…");
+    }
+
+    #[test]
+    fn truncate_start_keeps_the_end() {
+        assert_eq!(truncate_start("abc", 3, &narrow()), "abc");
+        assert_eq!(truncate_start("~/work/crate", 6, &narrow()), "…crate");
+    }
+
+    #[test]
+    fn truncate_start_does_not_split_wide_characters() {
+        assert_eq!(truncate_start("a中文", 4, &narrow()), "…文");
+    }
+
+    #[test]
+    fn truncate_start_counts_wide_ellipsis_and_private_use_glyphs() {
+        let truncated = truncate_start("abcdef", 4, &ambiguous_wide());
+        assert_eq!(truncated, "…ef");
+        assert_eq!(str_width(&truncated, &ambiguous_wide()), 4);
+
+        let text = "\u{e0a0}ab\u{e0b0}";
+        assert_eq!(truncate_start(text, 4, &private_use_wide()), "…b\u{e0b0}");
+    }
+}